@@ -1,25 +1,47 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    ffi::{OsStr, OsString},
     hash::Hash,
+    path::PathBuf,
     process::Stdio,
-    sync::{Arc, Mutex},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
 };
 
 use anyhow::Context as _;
 use bollard::{
     container::{ListContainersOptions, LogsOptions, RemoveContainerOptions},
-    secret::ContainerInspectResponse,
+    secret::{
+        ContainerInspectResponse, ContainerStateStatusEnum, ContainerSummary, HealthStatusEnum,
+        MountPointTypeEnum, RestartPolicyNameEnum,
+    },
     Docker,
 };
-use docker_compose_types::Compose;
+use docker_compose_types::{Compose, DependsOnOptions, Extension, Labels, Service};
 use futures::{Stream, StreamExt};
 use indexmap::IndexMap;
-use tokio::{sync::mpsc::Sender, task::JoinHandle};
+use tokio::{
+    sync::mpsc::{Sender, UnboundedReceiver, UnboundedSender},
+    task::JoinHandle,
+};
 
 use ratatui::widgets::{ListState, ScrollbarState};
 use tokio::process::{Child, Command};
 
-use crate::handler::{AlternateScreenContent, DockerEvent, QueueType};
+use crate::{
+    audit::{AuditEntry, AuditLog, Outcome},
+    bookmarks::{self, Bookmarks},
+    diff::host_ports,
+    events::{spawn_event_stream, spawn_raw_event_stream, EventFeed, RawEvent},
+    handler::{AlternateScreenContent, Confirm, DockerEvent, Prompt, QueueType, SplitScreen},
+    probe::{published_ports, spawn_probes, PortProbes},
+    project::{normalize_project_name, ProjectFile},
+    stats::{spawn_stats_stream, spawn_usage_stream, ContainerStats, StatsHistory},
+    utils::{copy_to_clipboard, find_matches, fuzzy_match, parse_relative, split_timestamp},
+};
 
 bitflags::bitflags! {
     #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -29,10 +51,13 @@ bitflags::bitflags! {
         const PULL_ALWAYS = 1 << 3;
         const ABORT_ON_CONTAINER_FAILURE = 1 << 4;
         const NO_DEPS = 1 << 5;
+        const REMOVE_ORPHANS = 1 << 6;
+        const RENEW_ANON_VOLUMES = 1 << 7;
     }
 }
 
 impl DockerModifier {
+    /// The flags of `docker compose up`, the modifiers don't apply to the other commands.
     pub fn to_args(&self) -> Vec<&str> {
         let mut args = vec![];
         if self.contains(DockerModifier::BUILD) {
@@ -50,10 +75,185 @@ impl DockerModifier {
         if self.contains(DockerModifier::NO_DEPS) {
             args.push("--no-deps");
         }
+        if self.contains(DockerModifier::REMOVE_ORPHANS) {
+            args.push("--remove-orphans");
+        }
+        if self.contains(DockerModifier::RENEW_ANON_VOLUMES) {
+            args.push("--renew-anon-volumes");
+        }
         args
     }
 }
 
+/// The progress output type of docker compose, passed as `--progress`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Progress {
+    /// Plain text output, the most readable when captured.
+    #[default]
+    Plain,
+    /// Interactive output with cursor movements.
+    Tty,
+    /// No progress output at all.
+    Quiet,
+}
+
+impl Progress {
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            Progress::Plain => "plain",
+            Progress::Tty => "tty",
+            Progress::Quiet => "quiet",
+        }
+    }
+}
+
+/// What the activity feed of the project shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ActivityFilter {
+    #[default]
+    Both,
+    Events,
+    Logs,
+}
+
+impl ActivityFilter {
+    pub fn next(self) -> Self {
+        match self {
+            ActivityFilter::Both => ActivityFilter::Events,
+            ActivityFilter::Events => ActivityFilter::Logs,
+            ActivityFilter::Logs => ActivityFilter::Both,
+        }
+    }
+}
+
+/// An entry of the activity feed, a lifecycle event or a log line of a service.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Activity {
+    pub time: jiff::Timestamp,
+    pub service: String,
+    /// Whether it's a lifecycle event, not a log line.
+    pub event: bool,
+    pub text: String,
+}
+
+/// How the selected row of the service list stands out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum HighlightStyle {
+    /// Italic and bold text.
+    #[default]
+    Emphasis,
+    /// Swapped foreground and background colors across the whole row.
+    Reverse,
+    /// Underlined text.
+    Underline,
+}
+
+/// The order the focus moves between the quadrants of the details screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum FocusOrder {
+    /// Column by column, down the left side first.
+    #[default]
+    Columns,
+    /// Row by row, across the top first.
+    Rows,
+}
+
+impl FocusOrder {
+    pub fn next(self) -> Self {
+        match self {
+            FocusOrder::Columns => FocusOrder::Rows,
+            FocusOrder::Rows => FocusOrder::Columns,
+        }
+    }
+}
+
+/// The format of the exported logs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// The logs as they are shown.
+    #[default]
+    Text,
+    /// One JSON object per log line with the service, timestamp, stream and message fields.
+    Jsonl,
+}
+
+impl LogFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            LogFormat::Text => "log",
+            LogFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+/// The screen shown on startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum StartScreen {
+    /// The service list next to the logs of the selected service.
+    #[default]
+    Main,
+    /// One line per service with its state, health and image.
+    Dashboard,
+    /// The logs of the selected service on the whole screen.
+    Logs,
+}
+
+impl StartScreen {
+    pub fn content(&self) -> AlternateScreenContent {
+        match self {
+            StartScreen::Main => AlternateScreenContent::None,
+            StartScreen::Dashboard => AlternateScreenContent::Dashboard,
+            StartScreen::Logs => AlternateScreenContent::Logs,
+        }
+    }
+}
+
+/// How the service list is grouped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ServiceGrouping {
+    /// A flat list in the order of the compose file.
+    #[default]
+    Flat,
+    /// Grouped by the first profile of each service.
+    Profile,
+    /// Grouped by the depth in the `depends_on` hierarchy, the services without dependencies first.
+    Dependency,
+    /// Grouped by the value of the label set with `--group-label`.
+    Label,
+}
+
+/// The number of log entries kept in the interleaved logs of all services.
+pub const INTERLEAVED_LOGS: usize = 10_000;
+
+/// How long the last known info of a container is shown after inspecting it started failing.
+const STALE_INFO_GRACE: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// The number of log lines shown of a service that crashed right after starting.
+const CRASH_LOG_LINES: usize = 10;
+
+/// The labels compose puts on the containers it creates.
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+
+/// The bounds of the number of log lines fetched from the history.
+pub const MIN_TAIL: usize = 10;
+pub const MAX_TAIL: usize = 10_000;
+
+/// The number of ticks between probing the published ports, about 10 seconds.
+const PROBE_TICKS: usize = 40;
+
+/// The number of ticks a status message stays visible.
+const STATUS_TICKS: usize = 20;
+
+/// The number of ticks without input or changes before the eco mode considers the interface idle, 2 seconds.
+const ECO_IDLE_TICKS: usize = 8;
+/// The number of ticks between the redraws while idle in the eco mode, so the relative times still
+/// advance, about 5 seconds.
+const ECO_REDRAW_TICKS: usize = 20;
+
+/// The wall clock gap between two ticks that means the host was suspended in the meantime.
+const RESUME_GAP: jiff::SignedDuration = jiff::SignedDuration::from_secs(10);
+
 /// Application.
 #[derive(Debug)]
 pub struct App {
@@ -63,8 +263,12 @@ pub struct App {
     pub running: bool,
     /// Data associated with compose
     pub compose_content: ComposeList,
-    /// The name of the currently running Docker containers.
+    /// The name of the currently running Docker containers of the active project.
     pub running_container_names: Vec<String>,
+    /// The name of the Docker containers of the active project that exist, but are not running.
+    pub stopped_container_names: Vec<String>,
+    /// The names of the containers created by compose, keyed by their project and service labels.
+    pub labeled_containers: HashMap<(String, String), String>,
     /// The Docker client.
     pub docker: Docker,
     /// The target Docker Compose file name.
@@ -79,21 +283,196 @@ pub struct App {
     pub vertical_scroll_state: ScrollbarState,
     /// The vertical scroll state for the main list.
     pub vertical_scroll: usize,
+    /// Whether the logs stay scrolled to the bottom as new lines arrive, scrolling up turns it off.
+    pub auto_scroll: bool,
     /// The mapping of docker compose toplevel services to their real container names.
     pub container_name_mapping: IndexMap<usize, String>,
     /// The container info for each running container.
     pub container_info: IndexMap<usize, Option<ContainerInspectResponse>>,
+    /// Since when the info of each container is the last known one, because inspecting it failed.
+    pub info_stale_since: IndexMap<usize, std::time::Instant>,
     /// The full path to the docker-compose file.
     // FIXME: maybe this is enough and we can delete `target`?
     pub full_path: std::path::PathBuf,
+    /// The variables of the `.env` file next to the compose file.
+    pub dotenv: IndexMap<String, String>,
     /// The version of the Docker daemon.
     pub docker_version: String,
     /// The content of on alternate screen.
     pub alternate_screen_content: AlternateScreenContent,
     /// The state of the alternate screen (scrolls).
     pub alternate_screen: AlternateScreen,
+    /// The output of `docker compose ps --format json`, or the error of running it.
+    pub compose_ps: Result<String, String>,
     /// The number of services in the compose file.
     pub services_len: usize,
+    /// Whether to prefix log lines with their line number.
+    pub show_line_numbers: bool,
+    /// Whether the multi-line log entries, like stack traces, are collapsed to their first line.
+    pub compact_logs: bool,
+    /// Whether the logs wrap at any character instead of the word boundaries, packing long tokens densely.
+    pub wrap_chars: bool,
+    /// Whether the terminal is cleared before the next draw, repainting the whole screen.
+    pub redraw: bool,
+    /// Whether the scrollable areas show their scrollbar.
+    pub show_scrollbars: bool,
+    /// Whether moving past either end of the service list continues at the other end.
+    pub wrap_around: bool,
+    /// The symbol in front of the selected row of the service list.
+    pub highlight_symbol: String,
+    /// How the selected row of the service list stands out.
+    pub highlight_style: HighlightStyle,
+    /// The order (tab) moves the focus between the quadrants of the details screen.
+    pub focus_order: FocusOrder,
+    /// Whether the service list shows how long ago the running containers started.
+    pub show_uptime: bool,
+    /// Whether the details show the configured resource limits next to the usage.
+    pub show_limits: bool,
+    /// Whether to hide the services that are not running from the service list.
+    pub hide_stopped: bool,
+    /// The label selector the services have to match to be listed, like `tier=frontend,debug`.
+    pub label_selector: Option<String>,
+    /// The fuzzy query the service names have to match to be listed.
+    pub service_filter: Option<String>,
+    /// The indices of the services matching the service filter, in the order they are declared.
+    pub filtered_services: Vec<usize>,
+    /// The service selected before filtering, selected again when the filter is cleared.
+    filter_selection: Option<usize>,
+    /// How the service list is grouped.
+    pub grouping: ServiceGrouping,
+    /// The label the services are grouped by with [`ServiceGrouping::Label`].
+    pub group_label: Option<String>,
+    /// The groups whose services are hidden under their header.
+    pub collapsed_groups: HashSet<String>,
+    /// The progress output type of the spawned compose commands.
+    pub progress: Progress,
+    /// The Docker context passed to the compose commands.
+    pub docker_context: Option<String>,
+    /// The seconds the containers get to stop before they're killed, passed as `--timeout` to
+    /// the `down`, `stop` and `restart` commands. Docker's default applies if not set.
+    pub stop_timeout: Option<u32>,
+    /// The number of spawned compose commands that are still running.
+    pub in_flight: usize,
+    /// Whether the screen is redrawn only occasionally while nothing changes, to save power.
+    pub eco: bool,
+    /// The tick of the last input or change, for the eco mode.
+    last_activity: usize,
+    /// The number of log chunks received as of the last tick, for the eco mode.
+    seen_log_chunks: usize,
+    /// The wall clock time of the last tick, to notice when the host wakes up from sleep.
+    last_tick_at: Option<jiff::Timestamp>,
+    /// The command spawned last, picked up by the task waiting for it.
+    pub last_spawned: Option<CommandLine>,
+    /// The compose command that failed last, until it's retried successfully.
+    pub failed_command: Option<CommandLine>,
+    /// The process ids of the spawned compose commands, so they can be interrupted.
+    pub running_commands: RunningCommands,
+    /// The recent resource usage samples of each service.
+    pub stats_history: StatsHistory,
+    /// The resource usage stream of the selected service.
+    pub stats_stream: Option<(usize, JoinHandle<()>)>,
+    /// The latest CPU percent and memory bytes of each running service, shown in the info bar.
+    pub container_stats: ContainerStats,
+    /// The streams filling [`App::container_stats`], by the service index.
+    pub stats_streamer_handle: IndexMap<usize, JoinHandle<()>>,
+    /// Whether the published ports of the running services are probed periodically.
+    pub probe_ports: bool,
+    /// The lifecycle events of the containers of the active project.
+    pub events: EventFeed,
+    /// The stream of the lifecycle events of the active project.
+    pub event_stream: Option<JoinHandle<()>>,
+    /// Every event of the active project while the daemon events are shown.
+    pub daemon_events: EventFeed<RawEvent>,
+    /// The stream of the daemon events and the project it's filtered to, only running while they're shown.
+    pub daemon_event_stream: Option<(String, JoinHandle<()>)>,
+    /// What the activity feed shows.
+    pub activity_filter: ActivityFilter,
+    /// The latest results of probing the published ports.
+    pub port_probes: PortProbes,
+    /// The probes in progress, a new round only starts after they finish.
+    pub probe_handle: Option<JoinHandle<()>>,
+    /// The time the logs view should jump to on the next render.
+    pub log_jump: Option<jiff::Timestamp>,
+    /// The text highlighted in the logs, `n` and `N` move between its matches while it's set.
+    pub search_query: Option<String>,
+    /// The lines of the shown logs that contain the query, updated on every render.
+    pub search_matches: Vec<usize>,
+    /// The line of the match jumped to last.
+    search_line: Option<usize>,
+    /// The line the logs view should jump to on the next render.
+    pub search_jump: Option<usize>,
+    /// The text searched in the logs of every service, and the lines that contain it.
+    pub grep_query: String,
+    pub grep_matches: Vec<GrepMatch>,
+    /// The match selected in the results.
+    pub grep_selected: usize,
+    /// The number of ticks since the start, drives the animations.
+    pub ticks: usize,
+    /// A short informational message and the tick it was set at.
+    pub status_msg: Option<(String, usize)>,
+    /// The format of the exported logs.
+    pub log_format: LogFormat,
+    /// Whether to send desktop notifications when long running commands finish.
+    pub notify: bool,
+    /// The action waiting for the user's confirmation.
+    pub confirm: Option<Confirm>,
+    /// The text input being typed.
+    pub prompt: Option<Prompt>,
+    /// How long the error popup stays open before closing itself, manual-only if not set.
+    pub popup_timeout: Option<std::time::Duration>,
+    /// When the error popup closes itself, cancelled by interacting with it.
+    pub popup_deadline: Option<std::time::Instant>,
+    /// Whether errors are kept without opening the popup.
+    pub quiet: bool,
+    /// The id of the running compose command whose output the popup shows instead of an error.
+    pub live_output: Option<u32>,
+    /// The lines of output streamed by the running commands, with the id of their command.
+    pub output_tx: UnboundedSender<(u32, String)>,
+    output_rx: UnboundedReceiver<(u32, String)>,
+    /// The number of errors that arrived in quiet mode without opening the popup.
+    pub suppressed_errors: usize,
+    /// Whether destructive actions require typing the project name to confirm.
+    pub safe: bool,
+    /// Whether the logs pane shows the interleaved logs of all services instead of the selected one.
+    pub all_logs: bool,
+    /// The scroll position of the logs pane in the mode that's not active.
+    pub other_logs_scroll: usize,
+    /// The services whose logs are shown side by side, once at least two of them are picked.
+    pub split_panes: Vec<SplitPane>,
+    /// The bookmarked services of every project, saved on each change.
+    pub bookmarks: Bookmarks,
+    /// The index of the split pane the scrolling applies to.
+    pub split_focus: usize,
+    /// The logs view that was frozen, `None` for the logs of all services, and its number of
+    /// lines at that time. The lines that arrived since are marked.
+    pub log_marker: Option<(Option<usize>, usize)>,
+    /// Whether the marked logs view shows only the lines that arrived since the marker.
+    pub only_since_marker: bool,
+    /// The audit trail of the mutating actions, if it's enabled.
+    pub audit: Option<AuditLog>,
+    /// Whether the started services are checked for crashing right after a successful start.
+    pub crash_check: bool,
+    /// Whether quitting offers to run `docker compose down`.
+    pub down_on_exit: bool,
+    /// Whether `docker compose down` runs after the interface is closed.
+    pub quit_with_down: bool,
+    /// The index of the active compose project.
+    pub active_project: usize,
+    /// The state of the inactive compose projects, keyed by their index.
+    pub parked_projects: IndexMap<usize, ProjectState>,
+}
+
+/// The state of a compose project that's not currently active.
+#[derive(Debug)]
+pub struct ProjectState {
+    pub project_name: String,
+    pub compose_content: ComposeList,
+    pub container_name_mapping: IndexMap<usize, String>,
+    pub container_info: IndexMap<usize, Option<ContainerInspectResponse>>,
+    pub target: String,
+    pub full_path: std::path::PathBuf,
+    pub dotenv: IndexMap<String, String>,
+    pub services_len: usize,
 }
 
 #[derive(Debug)]
@@ -106,6 +485,20 @@ pub struct AlternateScreen {
     pub lower_left_scroll: usize,
     pub lower_right_scroll_state: ScrollbarState,
     pub lower_right_scroll: usize,
+    pub panel_scroll_state: ScrollbarState,
+    pub panel_scroll: usize,
+    /// Whether the details quadrants wrap their lines.
+    pub wrap: bool,
+    /// The horizontal scroll of the focused details quadrant, used when wrapping is off.
+    pub horizontal_scroll: usize,
+    /// Whether scrolling moves all four details quadrants together.
+    pub sync_scroll: bool,
+    /// Whether the labels on the details screen are grouped by their namespace.
+    pub group_labels: bool,
+    /// The label namespaces whose labels are hidden under their group header.
+    pub collapsed_label_groups: HashSet<String>,
+    /// The top-level entries of a bind mount's host directory, with the service and the index of the mount.
+    pub mount_listing: Option<(usize, usize, Vec<String>)>,
 }
 
 impl Default for AlternateScreen {
@@ -125,6 +518,14 @@ impl AlternateScreen {
             lower_left_scroll_state: ScrollbarState::default(),
             lower_right_scroll: 0,
             lower_right_scroll_state: ScrollbarState::default(),
+            panel_scroll: 0,
+            panel_scroll_state: ScrollbarState::default(),
+            wrap: true,
+            horizontal_scroll: 0,
+            sync_scroll: false,
+            group_labels: false,
+            collapsed_label_groups: HashSet::new(),
+            mount_listing: None,
         }
     }
 
@@ -137,6 +538,44 @@ impl AlternateScreen {
         self.lower_left_scroll_state = self.lower_left_scroll_state.position(0);
         self.lower_right_scroll = 0;
         self.lower_right_scroll_state = self.lower_right_scroll_state.position(0);
+        self.panel_scroll = 0;
+        self.panel_scroll_state = self.panel_scroll_state.position(0);
+        self.horizontal_scroll = 0;
+    }
+
+    /// Toggles wrapping in the details quadrants.
+    pub fn toggle_wrap(&mut self) {
+        self.wrap = !self.wrap;
+        self.horizontal_scroll = 0;
+    }
+
+    /// Toggles scrolling the details quadrants together, aligning them to the focused one's position.
+    pub fn toggle_sync_scroll(&mut self, focused: SplitScreen) {
+        self.sync_scroll = !self.sync_scroll;
+        if !self.sync_scroll {
+            return;
+        }
+        let position = match focused {
+            SplitScreen::UpperLeft => self.upper_left_scroll,
+            SplitScreen::LowerLeft => self.lower_left_scroll,
+            SplitScreen::UpperRight => self.upper_right_scroll,
+            SplitScreen::LowerRight => self.lower_right_scroll,
+        };
+        self.upper_left_scroll = position;
+        self.upper_left_scroll_state = self.upper_left_scroll_state.position(position);
+        self.lower_left_scroll = position;
+        self.lower_left_scroll_state = self.lower_left_scroll_state.position(position);
+        self.upper_right_scroll = position;
+        self.upper_right_scroll_state = self.upper_right_scroll_state.position(position);
+        self.lower_right_scroll = position;
+        self.lower_right_scroll_state = self.lower_right_scroll_state.position(position);
+    }
+
+    /// Toggles grouping the labels by namespace, scrolling back to the first label.
+    pub fn toggle_label_grouping(&mut self) {
+        self.group_labels = !self.group_labels;
+        self.upper_left_scroll = 0;
+        self.upper_left_scroll_state = self.upper_left_scroll_state.position(0);
     }
 }
 
@@ -145,6 +584,7 @@ pub struct StreamOptions {
     pub tail: String,
     pub all: bool,
     pub since: Option<i64>,
+    pub timestamps: bool,
 }
 
 impl StreamOptions {
@@ -153,6 +593,7 @@ impl StreamOptions {
             since: Some(since),
             all: false,
             tail: "50".into(),
+            timestamps: false,
         }
     }
 }
@@ -163,6 +604,7 @@ impl Default for StreamOptions {
             tail: "50".into(),
             all: false,
             since: None,
+            timestamps: false,
         }
     }
 }
@@ -175,6 +617,7 @@ impl From<StreamOptions> for LogsOptions<String> {
             stderr: true,
             tail: val.tail,
             since: val.since.unwrap_or_default(),
+            timestamps: val.timestamps,
             ..Default::default()
         };
 
@@ -221,24 +664,82 @@ pub struct ComposeList {
     pub logs_since: IndexMap<usize, StreamOptions>,
     /// The actual log contents of each service.
     pub logs: Arc<Mutex<IndexMap<usize, Vec<String>>>>,
+    /// The number of log chunks received, which tells if new logs arrived without looking at them.
+    pub log_chunks: Arc<AtomicUsize>,
+    /// The logs of every service in the order they arrived with the time they arrived at,
+    /// capped at [`INTERLEAVED_LOGS`] entries.
+    pub interleaved_logs: Arc<Mutex<VecDeque<(usize, jiff::Timestamp, String)>>>,
     /// The error message to display on the popup.
     pub error_msg: Option<String>,
     /// The stream options for the logs.
     pub stream_options: StreamOptions,
+    /// The maximum number of services to stream logs for at the same time.
+    pub max_streams: Option<usize>,
+    /// The relative time window of the logs as the user typed it, for the services that have one.
+    pub since_window: IndexMap<usize, String>,
+    /// The services with a live log stream, ordered from least to most recently viewed.
+    /// Only used when `max_streams` is set.
+    pub stream_lru: VecDeque<usize>,
+    /// The services starting all services is limited to, empty if it's not limited.
+    pub only_services: Vec<String>,
 }
 
-// TODO: Auto-scroll
 impl ComposeList {
+    pub fn new(compose: Compose) -> Self {
+        let mut state = ListState::default();
+        state.select_first();
+        Self {
+            compose,
+            state,
+            start_queued: Default::default(),
+            stop_queued: Default::default(),
+            modifiers: DockerModifier::empty(),
+            log_streamer_handle: Arc::new(Mutex::new(IndexMap::new())),
+            logs: Arc::new(Mutex::new(IndexMap::new())),
+            log_chunks: Arc::new(AtomicUsize::new(0)),
+            interleaved_logs: Arc::new(Mutex::new(VecDeque::new())),
+            logs_since: IndexMap::new(),
+            error_msg: None,
+            stream_options: StreamOptions::default(),
+            max_streams: None,
+            since_window: IndexMap::new(),
+            stream_lru: VecDeque::new(),
+            only_services: Vec::new(),
+        }
+    }
+
+    /// Limits the number of live log streams, keeping the selected service and the first ones in the file.
+    pub fn set_max_streams(&mut self, max_streams: Option<usize>, services_len: usize) {
+        self.max_streams = max_streams.filter(|max| *max > 0);
+        self.stream_lru.clear();
+        if let Some(max_streams) = self.max_streams {
+            let selected = self.state.selected().unwrap_or(0);
+            self.stream_lru.extend(
+                (0..services_len)
+                    .filter(|i| *i != selected)
+                    .take(max_streams - 1),
+            );
+            self.stream_lru.push_back(selected);
+        }
+    }
+
     pub fn start_log_stream(
         &mut self,
         idx: usize,
         id: &str,
         docker: bollard::Docker,
     ) -> anyhow::Result<()> {
-        let stream_options = self.logs_since.get(&idx).cloned().unwrap_or_default();
+        let mut stream_options = self
+            .logs_since
+            .get(&idx)
+            .cloned()
+            .unwrap_or_else(|| self.stream_options.clone());
+        stream_options.timestamps = self.stream_options.timestamps;
         let mut logs_stream = get_log_stream(id, &docker, stream_options);
 
         let log_messages = self.logs.clone();
+        let log_chunks = self.log_chunks.clone();
+        let interleaved = self.interleaved_logs.clone();
         let mut guard = self.log_streamer_handle.lock().unwrap();
         if let Some(handle) = guard.shift_remove(&idx) {
             handle.abort();
@@ -247,15 +748,187 @@ impl ComposeList {
             idx,
             tokio::spawn(async move {
                 while let Some(v) = logs_stream.next().await {
+                    {
+                        let mut interleaved = interleaved.lock().unwrap();
+                        if interleaved.len() == INTERLEAVED_LOGS {
+                            interleaved.pop_front();
+                        }
+                        interleaved.push_back((idx, jiff::Timestamp::now(), v.clone()));
+                    }
                     {
                         log_messages.lock().unwrap().entry(idx).or_default().push(v);
                     }
+                    log_chunks.fetch_add(1, Ordering::Relaxed);
                 }
             }),
         );
 
         Ok(())
     }
+
+    /// Drops the buffered logs of the service, from the interleaved logs too.
+    pub fn clear_logs(&self, idx: usize) {
+        self.logs.lock().unwrap().shift_remove(&idx);
+        self.interleaved_logs
+            .lock()
+            .unwrap()
+            .retain(|(i, _, _)| *i != idx);
+    }
+
+    /// Drops the buffered logs of every service.
+    pub fn clear_all_logs(&self) {
+        self.logs.lock().unwrap().clear();
+        self.interleaved_logs.lock().unwrap().clear();
+    }
+
+    /// Whether the service opted out of automatic log streaming with `x-dcr-logs: false`.
+    pub fn logs_opted_out(&self, idx: usize) -> bool {
+        let Ok(extension) = Extension::from_str("x-dcr-logs") else {
+            return false;
+        };
+        self.compose
+            .services
+            .0
+            .get_index(idx)
+            .and_then(|(_, service)| service.as_ref())
+            .and_then(|service| service.extensions.get(&extension))
+            .is_some_and(|value| value.as_bool() == Some(false))
+    }
+
+    /// Whether the service should have a live log stream without being selected.
+    pub fn should_stream(&self, idx: usize) -> bool {
+        !self.logs_opted_out(idx) && (self.max_streams.is_none() || self.stream_lru.contains(&idx))
+    }
+
+    /// Marks the service as the most recently viewed one, resuming its log stream if it was paused
+    /// or starting it if the service opted out of automatic streaming.
+    /// Pauses the least recently viewed streams that exceed `max_streams`.
+    pub fn touch_log_stream(
+        &mut self,
+        idx: usize,
+        id: &str,
+        docker: bollard::Docker,
+    ) -> anyhow::Result<()> {
+        let is_streaming = self.log_streamer_handle.lock().unwrap().contains_key(&idx);
+        if !is_streaming {
+            self.start_log_stream(idx, id, docker)?;
+        }
+
+        let Some(max_streams) = self.max_streams else {
+            return Ok(());
+        };
+        self.stream_lru.retain(|i| *i != idx);
+        self.stream_lru.push_back(idx);
+
+        while self.stream_lru.len() > max_streams {
+            if let Some(evicted) = self.stream_lru.pop_front() {
+                self.pause_log_stream(evicted);
+            }
+        }
+        Ok(())
+    }
+
+    /// Stops the log stream of the service, and makes sure it resumes from this point in time.
+    pub fn pause_log_stream(&mut self, idx: usize) {
+        if let Some(handle) = self.log_streamer_handle.lock().unwrap().shift_remove(&idx) {
+            handle.abort();
+            self.logs_since.insert(
+                idx,
+                StreamOptions::from_unix_timestamp(
+                    jiff::Timestamp::now()
+                        .duration_since(jiff::Timestamp::UNIX_EPOCH)
+                        .as_secs(),
+                ),
+            );
+        }
+    }
+}
+
+/// The spawned compose commands that are still running by their process id, with whether they were interrupted.
+#[derive(Debug, Clone, Default)]
+pub struct RunningCommands(Arc<Mutex<IndexMap<u32, bool>>>);
+
+impl RunningCommands {
+    /// Starts tracking the command, returns its process id.
+    pub fn track(&self, child: &Child) -> Option<u32> {
+        let pid = child.id()?;
+        self.0.lock().unwrap().insert(pid, false);
+        Some(pid)
+    }
+
+    /// Stops tracking the command, returns whether it was interrupted.
+    pub fn finish(&self, pid: Option<u32>) -> bool {
+        pid.and_then(|pid| self.0.lock().unwrap().shift_remove(&pid))
+            .unwrap_or(false)
+    }
+
+    /// Sends `SIGINT` to every running command, so compose can cancel gracefully. Returns their number.
+    pub fn interrupt_all(&self) -> usize {
+        let mut commands = self.0.lock().unwrap();
+        for (pid, interrupted) in commands.iter_mut() {
+            // SAFETY: kill has no memory safety requirements, at worst the process is already gone.
+            unsafe { libc::kill(*pid as libc::pid_t, libc::SIGINT) };
+            *interrupted = true;
+        }
+        commands.len()
+    }
+}
+
+/// The most matches searching the logs of every service collects.
+pub const MAX_GREP_MATCHES: usize = 1_000;
+
+/// A buffered log line of a service that contains the searched text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepMatch {
+    pub service: usize,
+    /// The line in the buffer of the service.
+    pub line: usize,
+    pub text: String,
+}
+
+/// A service shown in the split logs view, with its own scroll position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitPane {
+    pub service: usize,
+    pub scroll: usize,
+}
+
+/// The program and the arguments of a spawned compose command, so it can be run again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandLine {
+    program: OsString,
+    args: Vec<OsString>,
+}
+
+impl CommandLine {
+    pub fn of(command: &Command) -> Self {
+        let command = command.as_std();
+        Self {
+            program: command.get_program().to_owned(),
+            args: command.get_args().map(OsStr::to_owned).collect(),
+        }
+    }
+
+    /// A new command with the same program and arguments, with the output captured like the others.
+    pub fn command(&self) -> Command {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::null());
+        command
+    }
+}
+
+impl std::fmt::Display for CommandLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.program.to_string_lossy())?;
+        for arg in &self.args {
+            write!(f, " {}", arg.to_string_lossy())?;
+        }
+        Ok(())
+    }
 }
 
 // TODO: This is unnecessary, we can just use the IndexMap.
@@ -266,52 +939,225 @@ pub struct Queued {
 }
 
 impl App {
-    pub fn new(
-        project_name: String,
-        compose: Compose,
-        container_name_mapping: IndexMap<usize, String>,
-        running_container_names: Vec<String>,
-        docker: Docker,
-        target: String,
-        full_path: impl AsRef<std::path::Path>,
-        docker_version: String,
-    ) -> Self {
+    pub fn new(project: ProjectFile, docker: Docker, docker_version: String) -> Self {
+        let ProjectFile {
+            project_name,
+            compose,
+            container_name_mapping,
+            target,
+            full_path,
+            dotenv,
+        } = project;
         let services_len = compose.services.0.len();
-        let mut state = ListState::default();
-        state.select_first();
+        let (output_tx, output_rx) = tokio::sync::mpsc::unbounded_channel();
         Self {
             project_name,
-            compose_content: ComposeList {
-                compose,
-                state,
-                start_queued: Default::default(),
-                stop_queued: Default::default(),
-                modifiers: DockerModifier::empty(),
-                log_streamer_handle: Arc::new(Mutex::new(IndexMap::new())),
-                logs: Arc::new(Mutex::new(IndexMap::new())),
-                logs_since: IndexMap::new(),
-                error_msg: None,
-                stream_options: StreamOptions::default(),
-            },
+            compose_content: ComposeList::new(compose),
             container_name_mapping,
             show_popup: false,
             running: true,
-            running_container_names,
+            running_container_names: Vec::new(),
+            stopped_container_names: Vec::new(),
+            labeled_containers: HashMap::new(),
             docker,
             target,
             vertical_scroll: 0,
+            auto_scroll: false,
             vertical_scroll_state: ScrollbarState::default(),
             popup_scroll: 0,
             popup_scroll_state: ScrollbarState::default(),
             container_info: IndexMap::new(),
-            full_path: full_path.as_ref().to_path_buf(),
+            info_stale_since: IndexMap::new(),
+            full_path,
+            dotenv,
             docker_version,
             alternate_screen_content: AlternateScreenContent::None,
             alternate_screen: AlternateScreen::new(),
+            compose_ps: Ok(String::new()),
             services_len,
+            show_line_numbers: false,
+            highlight_symbol: String::from(">>"),
+            highlight_style: HighlightStyle::Emphasis,
+            focus_order: FocusOrder::Columns,
+            show_uptime: false,
+            compact_logs: false,
+            wrap_chars: false,
+            redraw: false,
+            show_scrollbars: true,
+            wrap_around: false,
+            show_limits: false,
+            hide_stopped: false,
+            label_selector: None,
+            service_filter: None,
+            filtered_services: Vec::new(),
+            filter_selection: None,
+            grouping: ServiceGrouping::default(),
+            group_label: None,
+            collapsed_groups: HashSet::new(),
+            progress: Progress::default(),
+            docker_context: None,
+            stop_timeout: None,
+            in_flight: 0,
+            eco: false,
+            last_activity: 0,
+            seen_log_chunks: 0,
+            last_tick_at: None,
+            last_spawned: None,
+            failed_command: None,
+            running_commands: RunningCommands::default(),
+            stats_history: StatsHistory::default(),
+            stats_stream: None,
+            container_stats: ContainerStats::default(),
+            stats_streamer_handle: IndexMap::new(),
+            probe_ports: false,
+            events: EventFeed::default(),
+            event_stream: None,
+            daemon_events: EventFeed::default(),
+            daemon_event_stream: None,
+            activity_filter: ActivityFilter::Both,
+            port_probes: PortProbes::default(),
+            probe_handle: None,
+            log_jump: None,
+            search_query: None,
+            search_matches: Vec::new(),
+            search_line: None,
+            search_jump: None,
+            grep_query: String::new(),
+            grep_matches: Vec::new(),
+            grep_selected: 0,
+            ticks: 0,
+            status_msg: None,
+            log_format: LogFormat::default(),
+            notify: false,
+            confirm: None,
+            prompt: None,
+            popup_timeout: None,
+            popup_deadline: None,
+            quiet: false,
+            live_output: None,
+            output_tx,
+            output_rx,
+            suppressed_errors: 0,
+            safe: false,
+            all_logs: false,
+            other_logs_scroll: 0,
+            split_panes: Vec::new(),
+            bookmarks: Bookmarks::new(),
+            split_focus: 0,
+            log_marker: None,
+            only_since_marker: false,
+            audit: None,
+            crash_check: true,
+            down_on_exit: false,
+            quit_with_down: false,
+            active_project: 0,
+            parked_projects: IndexMap::new(),
+        }
+    }
+
+    /// The number of loaded compose projects.
+    pub fn projects_len(&self) -> usize {
+        self.parked_projects.len() + 1
+    }
+
+    /// Loads an additional compose project, which can be activated with [`App::switch_project`].
+    pub fn add_project(&mut self, project: ProjectFile) {
+        let ProjectFile {
+            project_name,
+            compose,
+            container_name_mapping,
+            target,
+            full_path,
+            dotenv,
+        } = project;
+        let services_len = compose.services.0.len();
+        let mut compose_content = ComposeList::new(compose);
+        compose_content.set_max_streams(self.compose_content.max_streams, services_len);
+
+        let idx = self.projects_len();
+        self.parked_projects.insert(
+            idx,
+            ProjectState {
+                project_name,
+                compose_content,
+                container_name_mapping,
+                container_info: IndexMap::new(),
+                target,
+                full_path,
+                dotenv,
+                services_len,
+            },
+        );
+    }
+
+    /// Switches to the project `offset` positions away from the active one, wrapping around.
+    ///
+    /// The log streams of the project we switch away from are paused, and resumed when it's activated again.
+    pub async fn switch_project(&mut self, offset: isize) -> anyhow::Result<()> {
+        let len = self.projects_len() as isize;
+        let next = (self.active_project as isize + offset).rem_euclid(len) as usize;
+        let Some(incoming) = self.parked_projects.shift_remove(&next) else {
+            return Ok(());
+        };
+        let modifiers = self.compose_content.modifiers;
+
+        let mut outgoing = ProjectState {
+            project_name: std::mem::replace(&mut self.project_name, incoming.project_name),
+            compose_content: std::mem::replace(&mut self.compose_content, incoming.compose_content),
+            container_name_mapping: std::mem::replace(
+                &mut self.container_name_mapping,
+                incoming.container_name_mapping,
+            ),
+            container_info: std::mem::replace(&mut self.container_info, incoming.container_info),
+            target: std::mem::replace(&mut self.target, incoming.target),
+            full_path: std::mem::replace(&mut self.full_path, incoming.full_path),
+            dotenv: std::mem::replace(&mut self.dotenv, incoming.dotenv),
+            services_len: std::mem::replace(&mut self.services_len, incoming.services_len),
+        };
+        // The samples are keyed by the service index, which means a different service in the other project.
+        if let Some((_, handle)) = self.stats_stream.take() {
+            handle.abort();
+        }
+        self.stats_history.clear();
+        self.stop_all_stats_streaming();
+        self.port_probes.clear();
+        self.info_stale_since.clear();
+        self.log_marker = None;
+        self.only_since_marker = false;
+        // The panes refer to the services by index, which means different ones in the other project.
+        self.clear_split_panes();
+        self.start_event_stream();
+        let streaming = outgoing
+            .compose_content
+            .log_streamer_handle
+            .lock()
+            .unwrap()
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        for idx in streaming {
+            outgoing.compose_content.pause_log_stream(idx);
         }
+        self.parked_projects.insert(self.active_project, outgoing);
+        self.active_project = next;
+
+        self.compose_content.modifiers = modifiers;
+        // The filtered indices refer to the services of the other project, so the query is matched again.
+        self.filter_selection = None;
+        if let Some(filter) = self.service_filter.clone() {
+            self.set_service_filter(&filter);
+        }
+        self.reset_scroll();
+        self.start_all_log_streaming().await?;
+        self.fetch_all_container_info().await?;
+
+        Ok(())
     }
 
+    /// Inspects every container again.
+    ///
+    /// Inspecting can fail for a moment while a container is recreated, so the last known info is kept
+    /// and marked stale for [`STALE_INFO_GRACE`] instead of flashing "Not available" in between.
     pub async fn fetch_all_container_info(&mut self) -> anyhow::Result<()> {
         for (i, name) in &self.container_name_mapping {
             if let Ok(info) = self
@@ -320,129 +1166,1640 @@ impl App {
                 .await
             {
                 self.container_info.insert(*i, Some(info));
+                self.info_stale_since.shift_remove(i);
+            } else if matches!(self.container_info.get(i), Some(Some(_))) {
+                self.info_stale_since
+                    .entry(*i)
+                    .or_insert_with(std::time::Instant::now);
             } else {
                 self.container_info.insert(*i, None);
             }
         }
+        self.expire_stale_info();
 
         Ok(())
     }
 
-    pub fn reset_scroll(&mut self) {
-        self.vertical_scroll = 0;
-        self.vertical_scroll_state = self.vertical_scroll_state.position(0);
-        self.alternate_screen.reset_scrolls();
+    /// Drops the last known info of the containers that couldn't be inspected for longer than the grace period.
+    fn expire_stale_info(&mut self) {
+        let expired = self
+            .info_stale_since
+            .iter()
+            .filter(|(_, since)| since.elapsed() > STALE_INFO_GRACE)
+            .map(|(i, _)| *i)
+            .collect::<Vec<_>>();
+        for i in expired {
+            self.info_stale_since.shift_remove(&i);
+            self.container_info.insert(i, None);
+        }
     }
 
-    pub fn reset_popup_scroll(&mut self) {
-        self.popup_scroll_state = self.popup_scroll_state.position(0);
-        self.popup_scroll = 0;
+    /// How long the info of the container has been the last known one, if inspecting it failed.
+    pub fn info_stale_for(&self, idx: usize) -> Option<std::time::Duration> {
+        self.info_stale_since.get(&idx).map(|since| since.elapsed())
     }
 
-    pub fn clear_current_log(&mut self) {
-        if let Some(selected) = self.compose_content.state.selected() {
-            *self
-                .compose_content
-                .logs
-                .lock()
-                .unwrap()
-                .entry(selected)
-                .or_default() = Vec::new();
-
-            self.compose_content.logs_since.insert(
-                selected,
-                StreamOptions::from_unix_timestamp(
-                    jiff::Timestamp::now()
-                        .duration_since(jiff::Timestamp::UNIX_EPOCH)
-                        .as_secs(),
-                ),
-            );
-        }
+    /// Limits the number of live log streams, keeping the selected service and the first ones in the file.
+    pub fn set_max_streams(&mut self, max_streams: Option<usize>) {
+        self.compose_content
+            .set_max_streams(max_streams, self.services_len);
     }
 
-    pub async fn restart_log_streaming(&mut self) -> anyhow::Result<()> {
+    /// Resumes the log stream of the selected service if it was paused due to `max_streams`.
+    pub fn touch_selected_log_stream(&mut self) {
         let Some(selected) = self.compose_content.state.selected() else {
-            return Ok(());
+            return;
         };
         let Some(container_name) = self.container_name_mapping.get(&selected) else {
-            return Ok(());
+            return;
         };
-        self.compose_content
-            .start_log_stream(selected, container_name, self.docker.clone())?;
-
-        Ok(())
-    }
-
-    pub async fn start_all_log_streaming(&mut self) -> anyhow::Result<()> {
-        for (selected, container_name) in &self.container_name_mapping {
+        if let Err(e) =
             self.compose_content
-                .start_log_stream(*selected, container_name, self.docker.clone())
-                .with_context(|| format!("Failed to start log streaming for {container_name}"))?;
+                .touch_log_stream(selected, container_name, self.docker.clone())
+        {
+            self.set_error_log(e.to_string());
         }
-
-        Ok(())
     }
 
-    /// Handles the tick event of the terminal.
-    pub fn tick(&self) {}
+    /// Selects the first unhealthy service, or the first running one if every service is healthy.
+    /// Falls back to the first service if none of them are running.
+    pub fn select_first_interesting(&mut self) {
+        let unhealthy = self.container_info.iter().find_map(|(i, info)| {
+            let status = info.as_ref()?.state.as_ref()?.health.as_ref()?.status?;
+            (status == HealthStatusEnum::UNHEALTHY).then_some(*i)
+        });
+        let running = || {
+            self.container_name_mapping
+                .iter()
+                .find(|(_, name)| self.running_container_names.contains(name))
+                .map(|(i, _)| *i)
+        };
+        let target = unhealthy.or_else(running).unwrap_or(0);
+        self.compose_content.state.select(Some(target));
+        self.touch_selected_log_stream();
+    }
 
-    /// Set running to false to quit the application.
-    pub fn quit(&mut self) {
-        self.running = false;
+    /// Whether the service is bookmarked in the active project.
+    pub fn is_bookmarked(&self, idx: usize) -> bool {
+        let Some((name, _)) = self.compose_content.compose.services.0.get_index(idx) else {
+            return false;
+        };
+        self.bookmarks
+            .get(&self.project_name)
+            .is_some_and(|services| services.contains(name))
     }
 
-    pub fn set_error_log(&mut self, error: String) {
-        self.compose_content.error_msg = Some(error);
+    /// Bookmarks the selected service, or removes its bookmark, and saves the bookmarks.
+    pub fn toggle_bookmark(&mut self) {
+        let Some(selected) = self.compose_content.state.selected() else {
+            return;
+        };
+        let Some((name, _)) = self.compose_content.compose.services.0.get_index(selected) else {
+            return;
+        };
+        let name = name.clone();
+        let services = self.bookmarks.entry(self.project_name.clone()).or_default();
+        if let Some(position) = services.iter().position(|service| *service == name) {
+            services.remove(position);
+            self.set_status(format!("Removed the bookmark of {name}"));
+        } else {
+            services.push(name.clone());
+            self.set_status(format!("Bookmarked {name}"));
+        }
+        if let Err(e) = bookmarks::save(&self.bookmarks) {
+            self.report_error(format!("{e:#}"));
+        }
     }
 
-    pub fn clear_latest_error_log(&mut self) {
-        self.compose_content.error_msg = None;
+    /// Selects the next bookmarked service in the order they were bookmarked, even if the filters hide it.
+    pub fn next_bookmark(&mut self) {
+        let services = &self.compose_content.compose.services.0;
+        let bookmarked = self
+            .bookmarks
+            .get(&self.project_name)
+            .into_iter()
+            .flatten()
+            .filter_map(|name| services.get_index_of(name))
+            .collect::<Vec<_>>();
+        let selected = self.compose_content.state.selected();
+        let next = match bookmarked.iter().position(|idx| Some(*idx) == selected) {
+            Some(position) => bookmarked.get((position + 1) % bookmarked.len()),
+            None => bookmarked.first(),
+        };
+        let Some(next) = next.copied() else {
+            self.set_status(String::from(
+                "No bookmarked services, bookmark one with ctrl + a",
+            ));
+            return;
+        };
+        self.compose_content.state.select(Some(next));
+        self.touch_selected_log_stream();
+        self.reset_scroll();
+        if !self.is_visible(next) {
+            self.set_status(String::from(
+                "The bookmarked service is hidden by the filters",
+            ));
+        }
     }
 
-    pub fn toggle_modifier(&mut self, modifier: char) {
-        // SAFETY: The caller only passes numeric chars.
-        let code = 1 << (modifier as u8);
+    /// The `restart:` policy declared in the compose file for the given service, if any.
+    pub fn declared_restart_policy(&self, idx: usize) -> Option<&str> {
         self.compose_content
-            .modifiers
-            .toggle(DockerModifier::from_bits_truncate(code));
+            .compose
+            .services
+            .0
+            .get_index(idx)
+            .and_then(|(_, service)| service.as_ref())
+            .and_then(|service| service.restart.as_deref())
     }
 
-    pub fn up(&mut self, _tx: Sender<DockerEvent>) {
-        self.compose_content.state.select_previous();
+    /// The restart policy of the running container in the same format as the compose file uses.
+    pub fn runtime_restart_policy(&self, idx: usize) -> Option<String> {
+        let policy = self
+            .container_info
+            .get(&idx)?
+            .as_ref()?
+            .host_config
+            .as_ref()?
+            .restart_policy
+            .as_ref()?;
+        let policy = match (policy.name, policy.maximum_retry_count) {
+            (None | Some(RestartPolicyNameEnum::EMPTY), _) => String::from("no"),
+            (Some(RestartPolicyNameEnum::ON_FAILURE), Some(count)) if count > 0 => {
+                format!("on-failure:{count}")
+            }
+            (Some(name), _) => name.to_string(),
+        };
+        Some(policy)
     }
 
-    pub fn up_first(&mut self, _tx: Sender<DockerEvent>) {
-        self.compose_content.state.select_first();
+    /// The latest lifecycle event of the service's container, preferring the time it last exited.
+    pub fn last_container_event(&self, idx: usize) -> Option<(&'static str, jiff::Timestamp)> {
+        let state = self.container_info.get(&idx)?.as_ref()?.state.as_ref()?;
+        // Docker reports the zero time for events that never happened.
+        let parse = |time: Option<&str>| {
+            time?
+                .parse::<jiff::Timestamp>()
+                .ok()
+                .filter(|t| *t > jiff::Timestamp::UNIX_EPOCH)
+        };
+        parse(state.finished_at.as_deref())
+            .map(|t| ("exit", t))
+            .or_else(|| parse(state.started_at.as_deref()).map(|t| ("start", t)))
     }
 
-    pub fn down(&mut self, _tx: Sender<DockerEvent>) {
-        // The extra logic to stay at the last item if we are about to overflow.
-        // We may add a wrap-around feature in the future.
-        match self.compose_content.state.selected() {
-            Some(selected) if selected >= self.services_len.saturating_sub(1) => {
-                self.compose_content
-                    .state
-                    .select(Some(self.services_len.saturating_sub(1)));
+    /// Scrolls the logs of the selected service to the lines around its latest container event.
+    pub fn jump_to_container_event(&mut self) {
+        if !self.compose_content.stream_options.timestamps {
+            self.set_status(String::from(
+                "Turn on timestamps (t) to jump to container events",
+            ));
+            return;
+        }
+        let Some(selected) = self.compose_content.state.selected() else {
+            return;
+        };
+        match self.last_container_event(selected) {
+            Some((event, time)) => {
+                self.log_jump = Some(time);
+                self.auto_scroll = false;
+                self.set_status(format!("Jumped to the {event} at {time}"));
             }
-            Some(_) => self.compose_content.state.select_next(),
-            None => {}
+            None => self.set_status(String::from("No container events to jump to")),
         }
     }
 
-    pub fn down_last(&mut self, _tx: Sender<DockerEvent>) {
-        self.compose_content.state.select_last();
-    }
+    /// The entries of the Labels quadrant of the selected container, each with the namespace it belongs to.
+    ///
+    /// When grouping is on, every namespace starts with a header line, and the labels of collapsed
+    /// namespaces are left out.
+    pub fn label_entries(&self) -> Vec<(String, String)> {
+        let labels = self
+            .compose_content
+            .state
+            .selected()
+            .and_then(|selected| self.container_info.get(&selected)?.as_ref())
+            .and_then(|info| info.config.as_ref()?.labels.clone())
+            .unwrap_or_default();
+        if !self.alternate_screen.group_labels {
+            return labels
+                .into_iter()
+                .map(|(name, value)| (String::new(), format!("{name}: {value}")))
+                .collect();
+        }
+
+        let mut groups: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+        for (name, value) in labels {
+            groups
+                .entry(label_namespace(&name))
+                .or_default()
+                .push((name, value));
+        }
+        let mut entries = Vec::new();
+        for (namespace, mut labels) in groups {
+            labels.sort();
+            let collapsed = self
+                .alternate_screen
+                .collapsed_label_groups
+                .contains(&namespace);
+            let marker = if collapsed { '▶' } else { '▼' };
+            let title = if namespace.is_empty() {
+                "user labels"
+            } else {
+                &namespace
+            };
+            entries.push((
+                namespace.clone(),
+                format!("{marker} {title} ({})", labels.len()),
+            ));
+            if !collapsed {
+                entries.extend(
+                    labels
+                        .into_iter()
+                        .map(|(name, value)| (namespace.clone(), format!("  {name}: {value}"))),
+                );
+            }
+        }
+        entries
+    }
+
+    /// Collapses or expands the label namespace at the top of the Labels quadrant.
+    pub fn toggle_label_group(&mut self) {
+        if !self.alternate_screen.group_labels {
+            return;
+        }
+        let entries = self.label_entries();
+        let Some((namespace, _)) = entries.get(self.alternate_screen.upper_left_scroll) else {
+            return;
+        };
+        let collapsed = &mut self.alternate_screen.collapsed_label_groups;
+        if !collapsed.remove(namespace) {
+            collapsed.insert(namespace.clone());
+        }
+        // Keep the header of the toggled group at the top.
+        let header = entries
+            .iter()
+            .position(|(group, _)| group == namespace)
+            .unwrap_or_default();
+        self.alternate_screen.upper_left_scroll = header;
+        self.alternate_screen.upper_left_scroll_state = self
+            .alternate_screen
+            .upper_left_scroll_state
+            .position(header);
+    }
+
+    /// The last `n` non-empty log lines of the given service.
+    pub fn log_tail(&self, idx: usize, n: usize) -> Vec<String> {
+        let logs = self.compose_content.logs.lock().unwrap();
+        let Some(content) = logs.get(&idx) else {
+            return Vec::new();
+        };
+        let mut tail = content
+            .iter()
+            .rev()
+            .flat_map(|chunk| chunk.lines().rev())
+            .map(|line| {
+                if self.compose_content.stream_options.timestamps {
+                    split_timestamp(line).1
+                } else {
+                    line
+                }
+            })
+            .filter(|line| !line.trim().is_empty())
+            .take(n)
+            .map(String::from)
+            .collect::<Vec<_>>();
+        tail.reverse();
+        tail
+    }
+
+    /// The last `n` log lines of the given service that look like errors.
+    pub fn error_log_lines(&self, idx: usize, n: usize) -> Vec<String> {
+        let logs = self.compose_content.logs.lock().unwrap();
+        let Some(content) = logs.get(&idx) else {
+            return Vec::new();
+        };
+        let mut lines = content
+            .iter()
+            .rev()
+            .flat_map(|chunk| chunk.lines().rev())
+            .map(|line| {
+                if self.compose_content.stream_options.timestamps {
+                    split_timestamp(line).1
+                } else {
+                    line
+                }
+            })
+            .filter(|line| looks_like_error(line))
+            .take(n)
+            .map(String::from)
+            .collect::<Vec<_>>();
+        lines.reverse();
+        lines
+    }
+
+    /// The host ports of the given service that other services of the file publish too,
+    /// paired with the name of the other service.
+    pub fn port_conflicts(&self, idx: usize) -> Vec<(String, String)> {
+        let services = &self.compose_content.compose.services.0;
+        let Some((_, Some(service))) = services.get_index(idx) else {
+            return Vec::new();
+        };
+        let own = host_ports(service);
+        services
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != idx)
+            .filter_map(|(_, (name, other))| Some((name, other.as_ref()?)))
+            .flat_map(|(name, other)| {
+                host_ports(other)
+                    .into_iter()
+                    .filter(|port| own.contains(port))
+                    .map(move |port| (port, name.clone()))
+            })
+            .collect()
+    }
+
+    pub fn reset_scroll(&mut self) {
+        self.vertical_scroll = 0;
+        self.vertical_scroll_state = self.vertical_scroll_state.position(0);
+        self.alternate_screen.reset_scrolls();
+    }
+
+    pub fn reset_popup_scroll(&mut self) {
+        self.popup_scroll_state = self.popup_scroll_state.position(0);
+        self.popup_scroll = 0;
+    }
+
+    pub fn clear_current_log(&mut self) {
+        if let Some(selected) = self.compose_content.state.selected() {
+            self.compose_content.clear_logs(selected);
+            self.compose_content.since_window.shift_remove(&selected);
+
+            self.compose_content.logs_since.insert(
+                selected,
+                StreamOptions::from_unix_timestamp(
+                    jiff::Timestamp::now()
+                        .duration_since(jiff::Timestamp::UNIX_EPOCH)
+                        .as_secs(),
+                ),
+            );
+        }
+    }
+
+    /// Shows only the logs of the selected service in a relative time window like "5m",
+    /// or the default tail again if the window is empty.
+    pub fn set_since_window(&mut self, window: &str) -> anyhow::Result<()> {
+        let Some(selected) = self.compose_content.state.selected() else {
+            return Ok(());
+        };
+        let window = window.trim();
+        if window.is_empty() {
+            self.compose_content.logs_since.shift_remove(&selected);
+            self.compose_content.since_window.shift_remove(&selected);
+        } else {
+            let since = jiff::Timestamp::now().as_second() - parse_relative(window)?;
+            self.compose_content.logs_since.insert(
+                selected,
+                StreamOptions {
+                    since: Some(since),
+                    all: true,
+                    ..Default::default()
+                },
+            );
+            self.compose_content
+                .since_window
+                .insert(selected, window.to_string());
+        }
+
+        // The stream sends the whole window again, so the current buffer is replaced.
+        self.compose_content.clear_logs(selected);
+        self.reset_scroll();
+        let Some(container_name) = self.container_name_mapping.get(&selected) else {
+            return Ok(());
+        };
+        self.compose_content
+            .start_log_stream(selected, container_name, self.docker.clone())
+    }
+
+    /// Doubles or halves the number of log lines fetched from the history, within [`MIN_TAIL`] and [`MAX_TAIL`],
+    /// and fetches the logs of the selected service again with it.
+    ///
+    /// A cleared buffer or a time window of the selected service is dropped, so the new tail takes effect.
+    pub async fn resize_tail(&mut self, grow: bool) -> anyhow::Result<()> {
+        let options = &mut self.compose_content.stream_options;
+        let tail = options.tail.parse::<usize>().unwrap_or(MIN_TAIL);
+        let tail = if grow { tail * 2 } else { tail / 2 };
+        options.tail = tail.clamp(MIN_TAIL, MAX_TAIL).to_string();
+        if let Some(selected) = self.compose_content.state.selected() {
+            self.compose_content.logs_since.shift_remove(&selected);
+            self.compose_content.since_window.shift_remove(&selected);
+        }
+        self.reset_scroll();
+        self.restart_log_streaming().await?;
+        self.set_status(format!(
+            "Log tail: {} lines",
+            self.compose_content.stream_options.tail
+        ));
+        Ok(())
+    }
+
+    /// Establishes the log stream of the selected service again.
+    ///
+    /// The buffer is fetched again too, so the lines sent by the new stream are not duplicated.
+    pub async fn restart_log_streaming(&mut self) -> anyhow::Result<()> {
+        let Some(selected) = self.compose_content.state.selected() else {
+            return Ok(());
+        };
+        let Some(container_name) = self.container_name_mapping.get(&selected) else {
+            return Ok(());
+        };
+        self.compose_content.clear_logs(selected);
+        self.compose_content
+            .start_log_stream(selected, container_name, self.docker.clone())?;
+        self.set_status(String::from("Log stream restarted"));
+
+        Ok(())
+    }
+
+    pub async fn start_all_log_streaming(&mut self) -> anyhow::Result<()> {
+        let current = self.compose_content.state.selected();
+        for (selected, container_name) in &self.container_name_mapping {
+            // Services that opted out of streaming still stream while they are selected.
+            if !self.compose_content.should_stream(*selected) && current != Some(*selected) {
+                continue;
+            }
+            self.compose_content
+                .start_log_stream(*selected, container_name, self.docker.clone())
+                .with_context(|| format!("Failed to start log streaming for {container_name}"))?;
+        }
+        self.start_all_stats_streaming();
+
+        Ok(())
+    }
+
+    /// Streams the resource usage of every running service, and stops the streams of the ones
+    /// that are not running anymore.
+    pub fn start_all_stats_streaming(&mut self) {
+        let running = self
+            .container_name_mapping
+            .iter()
+            .filter(|(_, name)| self.running_container_names.contains(name))
+            .map(|(idx, name)| (*idx, name.clone()))
+            .collect::<IndexMap<_, _>>();
+        self.stats_streamer_handle.retain(|idx, handle| {
+            let keep = running.contains_key(idx) && !handle.is_finished();
+            if !keep {
+                handle.abort();
+            }
+            keep
+        });
+        self.container_stats
+            .lock()
+            .unwrap()
+            .retain(|idx, _| self.stats_streamer_handle.contains_key(idx));
+        for (idx, name) in running {
+            if !self.stats_streamer_handle.contains_key(&idx) {
+                let handle =
+                    spawn_usage_stream(idx, &name, &self.docker, self.container_stats.clone());
+                self.stats_streamer_handle.insert(idx, handle);
+            }
+        }
+    }
+
+    /// Stops every resource usage stream of the info bar, and forgets their numbers.
+    fn stop_all_stats_streaming(&mut self) {
+        for (_, handle) in self.stats_streamer_handle.drain(..) {
+            handle.abort();
+        }
+        self.container_stats.lock().unwrap().clear();
+    }
+
+    /// Handles the tick event of the terminal.
+    pub fn tick(&mut self) {
+        self.ticks = self.ticks.wrapping_add(1);
+        if self
+            .popup_deadline
+            .is_some_and(|deadline| deadline <= std::time::Instant::now())
+        {
+            self.popup_deadline = None;
+            self.show_popup = false;
+            self.reset_popup_scroll();
+        }
+        let log_chunks = self.compose_content.log_chunks.load(Ordering::Relaxed);
+        // The spinner and the status message change on their own.
+        if log_chunks != self.seen_log_chunks || self.in_flight > 0 || self.status().is_some() {
+            self.seen_log_chunks = log_chunks;
+            self.mark_active();
+        }
+        self.ensure_stats_stream();
+        self.ensure_daemon_event_stream();
+        self.expire_stale_info();
+        // Starting from the first tick, so the results show up right away.
+        if self.probe_ports && self.ticks.wrapping_sub(1).is_multiple_of(PROBE_TICKS) {
+            self.probe_published_ports();
+        }
+    }
+
+    /// Starts probing the published ports of the running services, unless the previous round is still going.
+    fn probe_published_ports(&mut self) {
+        if self
+            .probe_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+        {
+            return;
+        }
+        let targets = self
+            .container_info
+            .iter()
+            .filter(|(idx, _)| {
+                self.container_name_mapping
+                    .get(*idx)
+                    .is_some_and(|name| self.running_container_names.contains(name))
+            })
+            .filter_map(|(idx, info)| {
+                let addresses = published_ports(info.as_ref()?);
+                (!addresses.is_empty()).then_some((*idx, addresses))
+            })
+            .collect();
+        self.probe_handle = Some(spawn_probes(targets, self.port_probes.clone()));
+    }
+
+    /// Streams the resource usage of the selected service if it's running, stopping the previous stream.
+    fn ensure_stats_stream(&mut self) {
+        let Some(selected) = self.compose_content.state.selected() else {
+            return;
+        };
+        if self
+            .stats_stream
+            .as_ref()
+            .is_some_and(|(idx, handle)| *idx == selected && !handle.is_finished())
+        {
+            return;
+        }
+        let Some(name) = self
+            .container_name_mapping
+            .get(&selected)
+            .filter(|name| self.running_container_names.contains(name))
+        else {
+            return;
+        };
+        if let Some((_, handle)) = self.stats_stream.take() {
+            handle.abort();
+        }
+        let handle = spawn_stats_stream(selected, name, &self.docker, self.stats_history.clone());
+        self.stats_stream = Some((selected, handle));
+    }
+
+    /// Notes that something changed, so the eco mode redraws the screen.
+    pub fn mark_active(&mut self) {
+        self.last_activity = self.ticks;
+    }
+
+    /// Whether the screen needs to be drawn, always unless it's idle in the eco mode.
+    pub fn should_draw(&self) -> bool {
+        !self.eco
+            || self.ticks.wrapping_sub(self.last_activity) < ECO_IDLE_TICKS
+            || self.ticks.is_multiple_of(ECO_REDRAW_TICKS)
+    }
+
+    /// Streams the events of the active project while they're shown, and stops the stream once they're closed.
+    fn ensure_daemon_event_stream(&mut self) {
+        let shown = self.alternate_screen_content == AlternateScreenContent::DaemonEvents;
+        if shown
+            && self
+                .daemon_event_stream
+                .as_ref()
+                .is_some_and(|(project, _)| *project == self.project_name)
+        {
+            return;
+        }
+        if let Some((_, handle)) = self.daemon_event_stream.take() {
+            handle.abort();
+        }
+        if shown {
+            self.daemon_events.clear();
+            let handle = spawn_raw_event_stream(
+                &self.project_name,
+                &self.docker,
+                self.daemon_events.clone(),
+            );
+            self.daemon_event_stream = Some((self.project_name.clone(), handle));
+        }
+    }
+
+    /// Whether the wall clock jumped ahead since the previous tick, as it does when the host resumes from sleep.
+    pub fn resumed(&mut self) -> bool {
+        let now = jiff::Timestamp::now();
+        self.last_tick_at
+            .replace(now)
+            .is_some_and(|previous| now.duration_since(previous) > RESUME_GAP)
+    }
+
+    /// Establishes every stream again and refreshes the state, the ones from before a suspend are likely dead.
+    ///
+    /// The log buffers are fetched again too, so the lines sent by the new streams are not duplicated.
+    pub async fn reconnect(&mut self) -> anyhow::Result<()> {
+        self.compose_content.clear_all_logs();
+        // The stats and the daemon events are started again on the next tick, the usage on refresh.
+        if let Some((_, handle)) = self.stats_stream.take() {
+            handle.abort();
+        }
+        self.stop_all_stats_streaming();
+        if let Some((_, handle)) = self.daemon_event_stream.take() {
+            handle.abort();
+        }
+        self.start_event_stream();
+        self.refresh().await?;
+        self.set_status(String::from("Resumed from sleep, streams reconnected"));
+        Ok(())
+    }
+
+    /// Toggles Docker's log timestamps. The logs are fetched again, so the buffer is not a mix of both formats.
+    pub async fn toggle_timestamps(&mut self) -> anyhow::Result<()> {
+        let options = &mut self.compose_content.stream_options;
+        options.timestamps = !options.timestamps;
+        self.compose_content.clear_all_logs();
+        self.reset_scroll();
+        self.start_all_log_streaming().await
+    }
+
+    /// Adds the selected service to the split logs view, or removes it if it's already there.
+    pub fn toggle_split_pane(&mut self) {
+        let Some(selected) = self.compose_content.state.selected() else {
+            return;
+        };
+        match self
+            .split_panes
+            .iter()
+            .position(|pane| pane.service == selected)
+        {
+            Some(i) => {
+                self.split_panes.remove(i);
+            }
+            None => self.split_panes.push(SplitPane {
+                service: selected,
+                scroll: 0,
+            }),
+        }
+        self.split_focus = self
+            .split_focus
+            .min(self.split_panes.len().saturating_sub(1));
+    }
+
+    pub fn clear_split_panes(&mut self) {
+        self.split_panes.clear();
+        self.split_focus = 0;
+    }
+
+    /// Whether the logs pane is split, which takes at least two services.
+    pub fn split_active(&self) -> bool {
+        self.split_panes.len() >= 2 && !self.all_logs
+    }
+
+    /// Moves the focus to the next split pane, or to the previous one if `forward` is not set.
+    pub fn cycle_split_focus(&mut self, forward: bool) {
+        let len = self.split_panes.len().max(1);
+        self.split_focus = if forward {
+            (self.split_focus + 1) % len
+        } else {
+            (self.split_focus + len - 1) % len
+        };
+    }
+
+    /// Scrolls the focused split pane by `amount` lines, upwards if negative.
+    pub fn scroll_split_pane(&mut self, amount: isize) {
+        if let Some(pane) = self.split_panes.get_mut(self.split_focus) {
+            pane.scroll = pane.scroll.saturating_add_signed(amount);
+        }
+    }
+
+    /// Switches the logs pane between the selected service and all services, each keeping its scroll position.
+    pub fn toggle_all_logs(&mut self) {
+        self.all_logs = !self.all_logs;
+        std::mem::swap(&mut self.vertical_scroll, &mut self.other_logs_scroll);
+        self.vertical_scroll_state = self.vertical_scroll_state.position(self.vertical_scroll);
+    }
+
+    /// The logs shown in the logs pane, of the selected service or every service.
+    pub fn displayed_logs(&self) -> Vec<String> {
+        if self.all_logs {
+            return self.interleaved_logs();
+        }
+        let selected = self.compose_content.state.selected().unwrap_or(0);
+        self.compose_content
+            .logs
+            .lock()
+            .unwrap()
+            .get(&selected)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The logs view currently shown, `None` for the logs of all services.
+    fn log_view(&self) -> Option<usize> {
+        if self.all_logs {
+            None
+        } else {
+            Some(self.compose_content.state.selected().unwrap_or(0))
+        }
+    }
+
+    /// Highlights the query in the logs, an empty query clears the search.
+    pub fn set_search_query(&mut self, query: &str) {
+        if query.is_empty() {
+            self.clear_search();
+        } else {
+            self.search_query = Some(query.to_string());
+        }
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search_query = None;
+        self.search_matches.clear();
+        self.search_line = None;
+        self.search_jump = None;
+    }
+
+    /// Scrolls the logs to the next or the previous line containing the query, wrapping around at the ends.
+    pub fn jump_to_search_match(&mut self, forward: bool) {
+        if self.search_query.is_none() {
+            return;
+        }
+        let current = self.search_line;
+        let line = if forward {
+            self.search_matches
+                .iter()
+                .find(|line| current.is_none_or(|current| **line > current))
+                .or(self.search_matches.first())
+        } else {
+            self.search_matches
+                .iter()
+                .rev()
+                .find(|line| current.is_none_or(|current| **line < current))
+                .or(self.search_matches.last())
+        };
+        let Some(line) = line.copied() else {
+            self.set_status(String::from("No matches"));
+            return;
+        };
+        self.search_line = Some(line);
+        self.search_jump = Some(line);
+        self.auto_scroll = false;
+        let position = self
+            .search_matches
+            .iter()
+            .position(|l| *l == line)
+            .unwrap_or(0);
+        self.set_status(format!(
+            "Match {}/{}",
+            position + 1,
+            self.search_matches.len()
+        ));
+    }
+
+    /// Searches the buffered logs of every service for the query, ignoring the case.
+    pub fn grep(&mut self, query: &str) {
+        self.grep_query = query.to_string();
+        self.grep_selected = 0;
+        self.grep_matches.clear();
+        if query.is_empty() {
+            return;
+        }
+        let logs = self.compose_content.logs.lock().unwrap();
+        let mut services = logs.keys().copied().collect::<Vec<_>>();
+        // In the order of the compose file, the buffers are in the order the streams started.
+        services.sort_unstable();
+        'services: for service in services {
+            let content = logs[&service].concat();
+            for (line, text) in content.lines().enumerate() {
+                if self.grep_matches.len() == MAX_GREP_MATCHES {
+                    break 'services;
+                }
+                if !find_matches(text, query).is_empty() {
+                    self.grep_matches.push(GrepMatch {
+                        service,
+                        line,
+                        text: text.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Moves the selection in the search results of every service.
+    pub fn move_grep_selection(&mut self, amount: isize) {
+        let last = self.grep_matches.len().saturating_sub(1);
+        self.grep_selected = self.grep_selected.saturating_add_signed(amount).min(last);
+    }
+
+    /// Selects the service of the selected search result and scrolls its logs to the matching line,
+    /// keeping the query highlighted so `n` and `N` continue from there.
+    pub fn open_grep_match(&mut self) {
+        let Some(found) = self.grep_matches.get(self.grep_selected).cloned() else {
+            return;
+        };
+        self.alternate_screen_content = AlternateScreenContent::None;
+        if self.all_logs {
+            self.toggle_all_logs();
+        }
+        self.compose_content.state.select(Some(found.service));
+        self.touch_selected_log_stream();
+        self.search_query = Some(self.grep_query.clone());
+        self.search_line = Some(found.line);
+        self.search_jump = Some(found.line);
+        self.auto_scroll = false;
+        if !self.is_visible(found.service) {
+            self.set_status(String::from("The service is hidden by the filters"));
+        }
+    }
+
+    /// Freezes the logs view at its current length, or unfreezes it.
+    pub fn toggle_log_marker(&mut self) {
+        if self.log_marker.take().is_some() {
+            self.only_since_marker = false;
+            return;
+        }
+        let lines = self.displayed_logs().concat().lines().count();
+        self.log_marker = Some((self.log_view(), lines));
+    }
+
+    /// Switches between the whole marked logs view and only the lines that arrived since the marker.
+    pub fn toggle_only_since_marker(&mut self) {
+        if self.log_marker_line().is_none() {
+            self.set_status(String::from("Mark the logs with (H) first"));
+            return;
+        }
+        self.only_since_marker = !self.only_since_marker;
+        self.vertical_scroll = 0;
+        self.vertical_scroll_state = self.vertical_scroll_state.position(0);
+    }
+
+    /// The line the marker goes before in the shown logs, if the shown view was frozen.
+    pub fn log_marker_line(&self) -> Option<usize> {
+        self.log_marker
+            .filter(|(view, _)| *view == self.log_view())
+            .map(|(_, lines)| lines)
+    }
+
+    /// The logs of every service in the order they arrived, each line prefixed with the service name.
+    ///
+    /// The name goes after Docker's timestamp, so the timestamps are still recognized.
+    pub fn interleaved_logs(&self) -> Vec<String> {
+        let services = &self.compose_content.compose.services.0;
+        let width = services
+            .keys()
+            .map(|name| name.len())
+            .max()
+            .unwrap_or_default();
+        self.compose_content
+            .interleaved_logs
+            .lock()
+            .unwrap()
+            .iter()
+            .flat_map(|(idx, _, chunk)| {
+                let name = services
+                    .get_index(*idx)
+                    .map(|(name, _)| name.as_str())
+                    .unwrap_or_default();
+                chunk
+                    .lines()
+                    .map(|line| {
+                        let (_, message) = split_timestamp(line);
+                        let timestamp = &line[..line.len() - message.len()];
+                        format!("{timestamp}{name:<width$} | {message}\n")
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Starts streaming the lifecycle events of the active project, replacing the previous stream.
+    pub fn start_event_stream(&mut self) {
+        if let Some(handle) = self.event_stream.take() {
+            handle.abort();
+        }
+        self.events.clear();
+        self.event_stream = Some(spawn_event_stream(
+            &self.project_name,
+            &self.docker,
+            self.events.clone(),
+        ));
+    }
+
+    /// The lifecycle events and the log lines of every service in chronological order, as the
+    /// activity filter allows. Log lines are ordered by Docker's timestamp if they have one,
+    /// otherwise by the time they arrived. Entries with the same time keep their order.
+    pub fn activity(&self) -> Vec<Activity> {
+        let mut activity = Vec::new();
+        if self.activity_filter != ActivityFilter::Logs {
+            activity.extend(self.events.events().into_iter().map(|event| Activity {
+                time: event.time,
+                service: event.service,
+                event: true,
+                text: event.action,
+            }));
+        }
+        if self.activity_filter != ActivityFilter::Events {
+            let services = &self.compose_content.compose.services.0;
+            let interleaved = self.compose_content.interleaved_logs.lock().unwrap();
+            for (idx, received, chunk) in interleaved.iter() {
+                let service = services
+                    .get_index(*idx)
+                    .map(|(name, _)| name.clone())
+                    .unwrap_or_default();
+                activity.extend(chunk.lines().map(|line| {
+                    let (timestamp, message) = split_timestamp(line);
+                    Activity {
+                        time: timestamp.unwrap_or(*received),
+                        service: service.clone(),
+                        event: false,
+                        text: message.to_string(),
+                    }
+                }));
+            }
+        }
+        activity.sort_by_key(|entry| entry.time);
+        activity
+    }
+
+    /// Copies the logs of the selected service to the clipboard, optionally wrapped in a markdown code block.
+    pub fn copy_logs(&self, fenced: bool) -> anyhow::Result<()> {
+        let selected = self.compose_content.state.selected().unwrap_or(0);
+        let mut logs = self
+            .compose_content
+            .logs
+            .lock()
+            .unwrap()
+            .get(&selected)
+            .map(|content| content.join(""))
+            .unwrap_or_default();
+        if fenced {
+            if !logs.ends_with('\n') {
+                logs.push('\n');
+            }
+            logs = format!("```\n{logs}```\n");
+        }
+        copy_to_clipboard(&logs).context("Failed to copy logs to the clipboard")
+    }
+
+    /// Writes the logs of the selected service, or every service if `all` is set, to a file in the
+    /// current directory. Returns the name of the file.
+    pub fn export_logs(&self, all: bool) -> anyhow::Result<String> {
+        let selected = self.compose_content.state.selected().unwrap_or(0);
+        let services = &self.compose_content.compose.services.0;
+        let timestamps = self.compose_content.stream_options.timestamps;
+        let logs = self.compose_content.logs.lock().unwrap();
+
+        let mut output = String::new();
+        for (idx, content) in logs.iter() {
+            if !all && *idx != selected {
+                continue;
+            }
+            let Some((service, _)) = services.get_index(*idx) else {
+                continue;
+            };
+            match self.log_format {
+                LogFormat::Text if all => {
+                    for line in content.iter().flat_map(|chunk| chunk.lines()) {
+                        output.push_str(&format!("{service} | {line}\n"));
+                    }
+                }
+                LogFormat::Text => output.push_str(&content.join("")),
+                LogFormat::Jsonl => {
+                    for line in content.iter().flat_map(|chunk| chunk.lines()) {
+                        let (timestamp, message) = if timestamps {
+                            split_timestamp(line)
+                        } else {
+                            (None, line)
+                        };
+                        // The streams are merged when the logs are collected, so we can't tell them apart.
+                        let entry = serde_json::json!({
+                            "service": service,
+                            "timestamp": timestamp.map(|t| t.to_string()),
+                            "stream": null,
+                            "message": message,
+                        });
+                        output.push_str(&format!("{entry}\n"));
+                    }
+                }
+            }
+        }
+
+        let scope = if all {
+            "all"
+        } else {
+            services
+                .get_index(selected)
+                .map(|(service, _)| service.as_str())
+                .unwrap_or("unknown")
+        };
+        let file_name = format!(
+            "{}-{scope}-{}.{}",
+            self.project_name,
+            jiff::Timestamp::now().as_second(),
+            self.log_format.extension()
+        );
+        std::fs::write(&file_name, output)
+            .with_context(|| format!("Failed to export the logs to {file_name}"))?;
+        Ok(file_name)
+    }
+
+    /// Shows a short informational message in the legend for a few seconds.
+    pub fn set_status(&mut self, msg: String) {
+        self.status_msg = Some((msg, self.ticks));
+    }
+
+    /// The informational message, if it's still fresh.
+    pub fn status(&self) -> Option<&str> {
+        self.status_msg
+            .as_ref()
+            .filter(|(_, set_at)| self.ticks.wrapping_sub(*set_at) < STATUS_TICKS)
+            .map(|(msg, _)| msg.as_str())
+    }
+
+    pub fn toggle_line_numbers(&mut self) {
+        self.show_line_numbers = !self.show_line_numbers;
+    }
+
+    /// Set running to false to quit the application.
+    pub fn quit(&mut self) {
+        self.running = false;
+    }
+
+    pub fn set_error_log(&mut self, error: String) {
+        self.live_output = None;
+        self.compose_content.error_msg = Some(error);
+    }
+
+    /// Appends a line of the running command's output to the popup, which opens with the first line unless quiet.
+    ///
+    /// While the popup shows the output of a command, the lines of the others are left out.
+    pub fn append_output(&mut self, id: u32, line: String) {
+        match self.live_output {
+            Some(live) if live != id => return,
+            Some(_) => {}
+            None => {
+                self.live_output = Some(id);
+                self.compose_content.error_msg = Some(String::new());
+                self.reset_popup_scroll();
+                if !self.quiet {
+                    self.open_popup();
+                }
+            }
+        }
+        let output = self
+            .compose_content
+            .error_msg
+            .get_or_insert_with(String::new);
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    /// Shows the lines of output the running commands streamed since the last call.
+    pub fn drain_output(&mut self) {
+        while let Ok((id, line)) = self.output_rx.try_recv() {
+            self.append_output(id, line);
+        }
+    }
+
+    /// Closes the popup showing the output of the command if it succeeded. If it failed, the
+    /// output is kept with the failure appended, and reported like the other errors.
+    pub fn finish_output(&mut self, id: u32, error: Option<String>) {
+        // The last lines may still be waiting in the other channel.
+        self.drain_output();
+        if self.live_output != Some(id) {
+            // The popup didn't show this command, so its errors weren't seen yet.
+            if let Some(error) = error {
+                self.report_error(error);
+            }
+            return;
+        }
+        self.live_output = None;
+        if error.is_none() {
+            self.show_popup = false;
+            self.clear_latest_error_log();
+            self.reset_popup_scroll();
+            return;
+        }
+        let output = self
+            .compose_content
+            .error_msg
+            .get_or_insert_with(String::new);
+        output.push_str("\nThe command failed.");
+        if self.quiet {
+            self.suppressed_errors += 1;
+        } else {
+            self.open_popup();
+        }
+    }
+
+    /// Stores the error of a compose command, and shows it on the popup unless quiet mode is on.
+    pub fn report_error(&mut self, error: String) {
+        self.set_error_log(error);
+        if self.quiet {
+            self.suppressed_errors += 1;
+        } else {
+            self.open_popup();
+        }
+    }
+
+    /// Reports the started containers that already failed, with their exit code and last log lines.
+    ///
+    /// Containers of other projects are skipped, as the active project may have changed since.
+    pub fn report_crashed(&mut self, started: &[String]) {
+        let crashed = started
+            .iter()
+            .filter_map(|name| {
+                let (&idx, _) = self
+                    .container_name_mapping
+                    .iter()
+                    .find(|(_, mapped)| *mapped == name)?;
+                let state = self.container_info.get(&idx)?.as_ref()?.state.as_ref()?;
+                // One-off services like migrations exit with 0 on purpose.
+                let crashed = match state.status {
+                    Some(ContainerStateStatusEnum::EXITED) => state.exit_code != Some(0),
+                    Some(ContainerStateStatusEnum::DEAD) => true,
+                    _ => false,
+                };
+                crashed.then(|| {
+                    let service = self.compose_content.compose.services.0.get_index(idx);
+                    let service = service.map(|(service, _)| service.as_str()).unwrap_or(name);
+                    let exit_code = state
+                        .exit_code
+                        .map(|code| code.to_string())
+                        .unwrap_or_else(|| String::from("unknown"));
+                    let mut report =
+                        format!("{service} exited with code {exit_code} right after starting.");
+                    for line in self.log_tail(idx, CRASH_LOG_LINES) {
+                        report.push_str(&format!("\n  {line}"));
+                    }
+                    report
+                })
+            })
+            .collect::<Vec<_>>();
+        if !crashed.is_empty() {
+            self.report_error(crashed.join("\n\n"));
+        }
+    }
+
+    /// Shows the error popup, which closes itself after `popup_timeout` if it's set.
+    pub fn open_popup(&mut self) {
+        self.show_popup = true;
+        self.popup_deadline = self
+            .popup_timeout
+            .map(|timeout| std::time::Instant::now() + timeout);
+    }
+
+    /// Toggles quiet mode. When it's turned off, the latest suppressed error is shown.
+    pub fn toggle_quiet(&mut self) {
+        self.quiet = !self.quiet;
+        if !self.quiet && self.suppressed_errors > 0 && self.compose_content.error_msg.is_some() {
+            self.open_popup();
+        }
+        self.suppressed_errors = 0;
+    }
+
+    pub fn clear_latest_error_log(&mut self) {
+        self.compose_content.error_msg = None;
+    }
+
+    /// Toggles the modifier of the number key, `1` is the first flag.
+    pub fn toggle_modifier(&mut self, modifier: char) {
+        let Some(bit) = modifier.to_digit(10).filter(|bit| (1..8).contains(bit)) else {
+            return;
+        };
+        self.compose_content
+            .modifiers
+            .toggle(DockerModifier::from_bits_truncate(1 << bit));
+    }
+
+    /// Whether the service is shown in the service list.
+    pub fn is_visible(&self, idx: usize) -> bool {
+        self.passes_filters(idx)
+            && self
+                .group_of(idx)
+                .is_none_or(|group| !self.collapsed_groups.contains(&group))
+    }
+
+    /// Whether the service passes the stopped, the label and the name filters, regardless of its group being collapsed.
+    pub fn passes_filters(&self, idx: usize) -> bool {
+        let running = || {
+            self.container_name_mapping
+                .get(&idx)
+                .is_some_and(|name| self.running_container_names.contains(name))
+        };
+        (!self.hide_stopped || running())
+            && self.matches_label_selector(idx)
+            && (self.service_filter.is_none() || self.filtered_services.contains(&idx))
+    }
+
+    /// The name of the group the service belongs to, if the list is grouped.
+    pub fn group_of(&self, idx: usize) -> Option<String> {
+        let services = &self.compose_content.compose.services.0;
+        let (_, service) = services.get_index(idx)?;
+        match self.grouping {
+            ServiceGrouping::Flat => None,
+            ServiceGrouping::Profile => Some(
+                service
+                    .as_ref()
+                    .and_then(|service| service.profiles.first().cloned())
+                    .unwrap_or_else(|| String::from("no profile")),
+            ),
+            ServiceGrouping::Dependency => {
+                let depth = dependency_depth(&self.compose_content.compose, idx, &mut Vec::new());
+                Some(if depth == 0 {
+                    String::from("no dependencies")
+                } else {
+                    format!("dependency depth {depth}")
+                })
+            }
+            ServiceGrouping::Label => {
+                let key = self.group_label.as_deref()?;
+                Some(
+                    service
+                        .as_ref()
+                        .and_then(|service| service_label(service, key).flatten())
+                        .unwrap_or_else(|| format!("no {key}")),
+                )
+            }
+        }
+    }
+
+    /// The groups of the service list with the services passing the filters, or `None` if the list is flat.
+    ///
+    /// Groups are ordered by their first service, by depth when grouped by dependencies.
+    pub fn service_groups(&self) -> Option<Vec<(String, Vec<usize>)>> {
+        if self.grouping == ServiceGrouping::Flat
+            || (self.grouping == ServiceGrouping::Label && self.group_label.is_none())
+        {
+            return None;
+        }
+        let mut groups: IndexMap<String, (usize, Vec<usize>)> = IndexMap::new();
+        for idx in (0..self.services_len).filter(|i| self.passes_filters(*i)) {
+            let Some(group) = self.group_of(idx) else {
+                continue;
+            };
+            let order = match self.grouping {
+                ServiceGrouping::Dependency => {
+                    dependency_depth(&self.compose_content.compose, idx, &mut Vec::new())
+                }
+                _ => 0,
+            };
+            groups
+                .entry(group)
+                .or_insert((order, Vec::new()))
+                .1
+                .push(idx);
+        }
+        // Stable, so the groups with the same order keep the order of their first service.
+        groups.sort_by(|_, (a, _), _, (b, _)| a.cmp(b));
+        Some(
+            groups
+                .into_iter()
+                .map(|(name, (_, services))| (name, services))
+                .collect(),
+        )
+    }
+
+    /// Switches to the next grouping of the service list, skipping the label grouping without `--group-label`.
+    pub fn cycle_grouping(&mut self) {
+        self.grouping = match self.grouping {
+            ServiceGrouping::Flat => ServiceGrouping::Profile,
+            ServiceGrouping::Profile => ServiceGrouping::Dependency,
+            ServiceGrouping::Dependency if self.group_label.is_some() => ServiceGrouping::Label,
+            ServiceGrouping::Dependency | ServiceGrouping::Label => ServiceGrouping::Flat,
+        };
+        self.collapsed_groups.clear();
+        self.ensure_visible_selection();
+    }
 
+    /// Collapses the group of the selected service, moving the selection to the next visible service.
+    pub fn collapse_selected_group(&mut self) {
+        let selected = self.compose_content.state.selected().unwrap_or(0);
+        let Some(group) = self.group_of(selected) else {
+            return;
+        };
+        let visible = self.visible_services();
+        let position = visible.iter().position(|i| *i == selected).unwrap_or(0);
+        self.collapsed_groups.insert(group);
+        let next = visible[position..]
+            .iter()
+            .chain(visible[..position].iter().rev())
+            .find(|i| self.is_visible(**i))
+            .copied();
+        if let Some(next) = next {
+            self.compose_content.state.select(Some(next));
+            self.touch_selected_log_stream();
+        }
+    }
+
+    /// Expands every collapsed group of the service list.
+    pub fn expand_all_groups(&mut self) {
+        self.collapsed_groups.clear();
+        self.ensure_visible_selection();
+    }
+
+    /// Whether the labels declared for the service match every requirement of the label selector.
+    /// A requirement is either `key=value`, or just `key` to match any value.
+    pub fn matches_label_selector(&self, idx: usize) -> bool {
+        let Some(selector) = &self.label_selector else {
+            return true;
+        };
+        let Some(service) = self
+            .compose_content
+            .compose
+            .services
+            .0
+            .get_index(idx)
+            .and_then(|(_, service)| service.as_ref())
+        else {
+            return false;
+        };
+        let label = |key: &str| service_label(service, key);
+        selector
+            .split(',')
+            .map(str::trim)
+            .filter(|requirement| !requirement.is_empty())
+            .all(|requirement| match requirement.split_once('=') {
+                Some((key, value)) => label(key.trim()).flatten().as_deref() == Some(value.trim()),
+                None => label(requirement).is_some(),
+            })
+    }
+
+    /// Lists only the services matching the label selector, or all of them if it's empty.
+    pub fn set_label_selector(&mut self, selector: &str) {
+        let selector = selector.trim();
+        self.label_selector = (!selector.is_empty()).then(|| selector.to_string());
+        self.ensure_visible_selection();
+    }
+
+    /// Lists only the services whose name fuzzy matches the query, or all of them if it's empty.
+    pub fn set_service_filter(&mut self, query: &str) {
+        if query.is_empty() {
+            self.clear_service_filter();
+            return;
+        }
+        if self.service_filter.is_none() {
+            self.filter_selection = self.compose_content.state.selected();
+        }
+        self.filtered_services = self
+            .compose_content
+            .compose
+            .services
+            .0
+            .keys()
+            .enumerate()
+            .filter(|(_, name)| fuzzy_match(name, query))
+            .map(|(i, _)| i)
+            .collect();
+        self.service_filter = Some(query.to_string());
+        self.ensure_visible_selection();
+    }
+
+    /// Lists all services again, selecting the one selected before filtering if it's still listed.
+    pub fn clear_service_filter(&mut self) {
+        self.service_filter = None;
+        self.filtered_services.clear();
+        if let Some(previous) = self.filter_selection.take() {
+            if self.is_visible(previous) && self.compose_content.state.selected() != Some(previous)
+            {
+                self.compose_content.state.select(Some(previous));
+                self.touch_selected_log_stream();
+                self.reset_scroll();
+            }
+        }
+        self.ensure_visible_selection();
+    }
+
+    /// The indices of the services shown in the service list, in the order they are listed.
+    pub fn visible_services(&self) -> Vec<usize> {
+        match self.service_groups() {
+            Some(groups) => groups
+                .into_iter()
+                .filter(|(name, _)| !self.collapsed_groups.contains(name))
+                .flat_map(|(_, services)| services)
+                .collect(),
+            None => (0..self.services_len)
+                .filter(|i| self.is_visible(*i))
+                .collect(),
+        }
+    }
+
+    /// Moves the selection to the first visible service if the selected one got hidden.
+    ///
+    /// Nothing is selected while no service is listed, so the actions can't hit a hidden one.
+    pub fn ensure_visible_selection(&mut self) {
+        let selected = self.compose_content.state.selected();
+        if selected.is_some_and(|selected| self.is_visible(selected)) {
+            return;
+        }
+        let first = self.visible_services().first().copied();
+        self.compose_content.state.select(first);
+        if first.is_some() {
+            self.touch_selected_log_stream();
+        }
+    }
+
+    pub fn toggle_hide_stopped(&mut self) {
+        self.hide_stopped = !self.hide_stopped;
+        self.ensure_visible_selection();
+    }
+
+    pub fn up(&mut self, _tx: Sender<DockerEvent>) {
+        let Some(selected) = self.compose_content.state.selected() else {
+            return;
+        };
+        let visible = self.visible_services();
+        let previous = visible
+            .iter()
+            .position(|i| *i == selected)
+            .and_then(|position| match position.checked_sub(1) {
+                Some(previous) => Some(visible[previous]),
+                None if self.wrap_around => visible.last().copied(),
+                None => None,
+            });
+        if let Some(previous) = previous {
+            self.compose_content.state.select(Some(previous));
+        }
+        self.touch_selected_log_stream();
+    }
+
+    pub fn up_first(&mut self, _tx: Sender<DockerEvent>) {
+        if let Some(first) = self.visible_services().first() {
+            self.compose_content.state.select(Some(*first));
+        }
+        self.touch_selected_log_stream();
+    }
+
+    pub fn down(&mut self, _tx: Sender<DockerEvent>) {
+        // We stay at the last item if we are about to overflow, unless wrapping around.
+        let Some(selected) = self.compose_content.state.selected() else {
+            return;
+        };
+        let visible = self.visible_services();
+        let next = visible
+            .iter()
+            .position(|i| *i == selected)
+            .and_then(|position| match visible.get(position + 1) {
+                Some(next) => Some(next),
+                None if self.wrap_around => visible.first(),
+                None => None,
+            });
+        if let Some(next) = next {
+            self.compose_content.state.select(Some(*next));
+        }
+        self.touch_selected_log_stream();
+    }
+
+    pub fn down_last(&mut self, _tx: Sender<DockerEvent>) {
+        if let Some(last) = self.visible_services().last() {
+            self.compose_content.state.select(Some(*last));
+        }
+        self.touch_selected_log_stream();
+    }
+
+    /// The arguments of `docker` that select the compose project, shared by every compose command.
+    fn compose_args(&self) -> Vec<&str> {
+        let mut args = vec![];
+        if let Some(context) = &self.docker_context {
+            args.extend(["--context", context.as_str()]);
+        }
+        args.extend([
+            "compose",
+            "--progress",
+            self.progress.as_arg(),
+            "-f",
+            &self.target,
+            // Every project runs its own commands, so they must not pick up another project's name.
+            "-p",
+            &self.project_name,
+        ]);
+        args
+    }
+
+    /// Spawns the command, remembering its command line so it can be retried if it fails.
+    fn spawn_recorded(&mut self, command: &mut Command) -> std::io::Result<Child> {
+        self.last_spawned = Some(CommandLine::of(command));
+        command.spawn()
+    }
+
+    /// Runs the last failed compose command again, with its audit record.
+    /// Reports the error instead if the command can't be spawned.
+    pub fn retry_failed(&mut self) -> Option<(Child, Option<AuditEntry>)> {
+        let failed = self.failed_command.clone()?;
+        self.set_status(format!("Retrying {failed}"));
+        let audit = self.audit.as_ref().map(|audit| {
+            let action = format!("retry {failed}");
+            audit.entry(&self.project_name, &action, Vec::new(), Vec::new())
+        });
+        match self.spawn_recorded(&mut failed.command()) {
+            Ok(child) => Some((child, audit)),
+            Err(e) => {
+                if let Some(audit) = audit {
+                    audit.finish(Outcome::Failure(None));
+                }
+                self.report_error(format!("Failed to retry {failed}: {e}"));
+                None
+            }
+        }
+    }
+
+    /// A `docker compose` command targeting the active compose file.
+    pub fn compose_command(&self) -> Command {
+        let mut command = Command::new("docker");
+        command.args(self.compose_args());
+        command
+    }
+
+    /// The command line that starting all services would run with the active modifiers.
+    pub fn up_all_command_line(&self) -> String {
+        let mut args = vec!["docker"];
+        args.extend(self.compose_args());
+        args.extend(["up", "-d"]);
+        args.extend(self.compose_content.modifiers.to_args());
+        args.extend(
+            self.compose_content
+                .only_services
+                .iter()
+                .map(String::as_str),
+        );
+        args.join(" ")
+    }
+
+    /// Copies the command line of starting all services to the clipboard.
+    pub fn copy_up_all_command_line(&self) -> anyhow::Result<()> {
+        copy_to_clipboard(&self.up_all_command_line())
+            .context("Failed to copy the command to the clipboard")
+    }
+
+    /// A summary of the session for bug reports: the versions, the compose file, the active
+    /// modifiers, the state of each service and the last error. Environment values are left out,
+    /// as they often hold secrets.
+    pub fn session_report(&self) -> String {
+        let modifiers = self.compose_content.modifiers.to_args();
+        let mut report = format!(
+            "dcr version: {}\nDocker version: {}\nDocker context: {}\nCompose file: {}\n\
+             Project: {}\nModifiers: {}\nServices:\n",
+            env!("CARGO_PKG_VERSION"),
+            self.docker_version,
+            self.docker_context.as_deref().unwrap_or("default"),
+            self.full_path.display(),
+            self.project_name,
+            if modifiers.is_empty() {
+                String::from("none")
+            } else {
+                modifiers.join(" ")
+            },
+        );
+        for (i, name) in self.compose_content.compose.services.0.keys().enumerate() {
+            let state = self
+                .container_info
+                .get(&i)
+                .and_then(|info| info.as_ref()?.state.as_ref()?.status)
+                .map(|status| status.to_string())
+                .unwrap_or_else(|| String::from("not created"));
+            report.push_str(&format!("  {name}: {state}\n"));
+        }
+        match &self.compose_content.error_msg {
+            Some(error) => report.push_str(&format!("Last error:\n{}\n", error.trim_end())),
+            None => report.push_str("Last error: none\n"),
+        }
+        report
+    }
+
+    /// Copies the session report to the clipboard.
+    pub fn copy_session_report(&mut self) -> anyhow::Result<()> {
+        copy_to_clipboard(&self.session_report())
+            .context("Failed to copy the session report to the clipboard")?;
+        self.set_status(String::from("Copied the session report"));
+        Ok(())
+    }
+
+    /// Whether the typed confirmation matches the project name.
+    pub fn confirms_project(&self, input: &str) -> bool {
+        input.trim() == self.project_name
+    }
+
+    /// The `--timeout` flag of the commands stopping containers, if the stop timeout is set.
+    fn timeout_args(&self) -> Vec<String> {
+        self.stop_timeout
+            .map(|timeout| vec![String::from("--timeout"), timeout.to_string()])
+            .unwrap_or_default()
+    }
+
+    /// Stops and removes the containers of the project, only the ones of `--services` if it's set.
     pub fn down_all(&mut self) -> Child {
-        let child = Command::new("docker")
-            .args(["compose", "-f", &self.target, "down"])
+        let mut command = self.compose_command();
+        command
+            .args(["down"])
+            .args(self.timeout_args())
+            .args(&self.compose_content.only_services)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .stdin(Stdio::null())
-            .spawn()
-            .unwrap();
-
-        child
+            .stdin(Stdio::null());
+        self.spawn_recorded(&mut command).unwrap()
     }
 
     pub fn queue(&mut self, queue_type: QueueType) {
@@ -488,7 +2845,7 @@ impl App {
                 self.compose_content.start_queued.state.extend(0..all);
             }
             QueueType::Stop => {
-                self.compose_content.start_queued.names = self.container_name_mapping.clone();
+                self.compose_content.stop_queued.names = self.container_name_mapping.clone();
                 self.compose_content.stop_queued.state.clear();
                 let all = self.compose_content.compose.services.0.len();
                 self.compose_content.stop_queued.state.extend(0..all);
@@ -496,85 +2853,382 @@ impl App {
         }
     }
 
+    /// The summary of the desktop notification about the command on the selected service,
+    /// or on every service if `all` is set. `None` if notifications are disabled.
+    pub fn notification(&self, command: &str, all: bool) -> Option<String> {
+        if !self.notify {
+            return None;
+        }
+        let service = if all {
+            "all services"
+        } else {
+            let selected = self.compose_content.state.selected()?;
+            self.compose_content
+                .compose
+                .services
+                .0
+                .get_index(selected)?
+                .0
+        };
+        Some(format!("{}: {command} {service}", self.project_name))
+    }
+
+    /// The audit record of the action on the selected service, or on every service if `all` is set.
+    /// `None` if there's no audit file.
+    pub fn audit_entry(&self, action: &str, all: bool) -> Option<AuditEntry> {
+        let audit = self.audit.as_ref()?;
+        let services = if all && !self.compose_content.only_services.is_empty() {
+            self.compose_content.only_services.clone()
+        } else if all {
+            self.compose_content
+                .compose
+                .services
+                .0
+                .keys()
+                .cloned()
+                .collect()
+        } else {
+            let selected = self.compose_content.state.selected()?;
+            let (service, _) = self
+                .compose_content
+                .compose
+                .services
+                .0
+                .get_index(selected)?;
+            vec![service.clone()]
+        };
+        let modifiers = self
+            .compose_content
+            .modifiers
+            .to_args()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        Some(audit.entry(&self.project_name, action, services, modifiers))
+    }
+
+    /// The `up` command of the selected service with the active modifiers.
+    pub fn up_command(&self) -> Option<Command> {
+        let selected = self.compose_content.state.selected()?;
+        let key = &self.compose_content.compose.services.0.keys()[selected];
+
+        let mut command = self.compose_command();
+        command
+            .args(["up", key, "-d"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::null())
+            .args(self.compose_content.modifiers.to_args());
+        Some(command)
+    }
+
     pub fn dc(&mut self, up: bool) -> Option<Child> {
         let selected = self.compose_content.state.selected()?;
         let key = &self.compose_content.compose.services.0.keys()[selected];
 
         let child = if up {
-            Command::new("docker")
-                .args(["compose", "-f", &self.target, "up", key, "-d"])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .stdin(Stdio::null())
-                .args(self.compose_content.modifiers.to_args())
-                .spawn()
-                .unwrap()
+            let mut command = self.up_command()?;
+            self.spawn_recorded(&mut command).unwrap()
         } else {
-            Command::new("docker")
-                .args(["compose", "-f", &self.target, "down", key])
+            let mut command = self.compose_command();
+            command
+                .args(["down", key])
+                .args(self.timeout_args())
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
-                .stdin(Stdio::null())
-                .spawn()
-                .unwrap()
+                .stdin(Stdio::null());
+            self.spawn_recorded(&mut command).unwrap()
         };
         Some(child)
     }
 
+    /// Pulls the latest image and recreates the selected service, regardless of the active modifiers.
+    pub fn pull_and_recreate(&mut self) -> Option<Child> {
+        let selected = self.compose_content.state.selected()?;
+        let key = &self.compose_content.compose.services.0.keys()[selected];
+        let modifiers = DockerModifier::PULL_ALWAYS | DockerModifier::FORCE_RECREATE;
+
+        let mut command = self.compose_command();
+        command
+            .args(["up", key, "-d"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::null())
+            .args(modifiers.to_args());
+        let child = self.spawn_recorded(&mut command).unwrap();
+        Some(child)
+    }
+
+    /// Stops the selected service, but keeps its container so it can be started again.
+    pub fn stop(&mut self) -> Option<Child> {
+        let selected = self.compose_content.state.selected()?;
+        let key = &self.compose_content.compose.services.0.keys()[selected];
+
+        let mut command = self.compose_command();
+        command
+            .args(["stop", key])
+            .args(self.timeout_args())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::null());
+        let child = self.spawn_recorded(&mut command).unwrap();
+        Some(child)
+    }
+
+    /// Starts the existing container of the selected service.
+    pub fn start(&mut self) -> Option<Child> {
+        let selected = self.compose_content.state.selected()?;
+        let key = &self.compose_content.compose.services.0.keys()[selected];
+
+        let mut command = self.compose_command();
+        command
+            .args(["start", key])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::null());
+        let child = self.spawn_recorded(&mut command).unwrap();
+        Some(child)
+    }
+
     pub fn all(&mut self) -> Child {
         let args = &self.compose_content.modifiers.to_args();
 
-        let child = Command::new("docker")
-            .args(["compose", "-f", &self.target, "up", "-d"])
+        let mut command = self.compose_command();
+        command
+            .args(["up", "-d"])
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .stdin(Stdio::null())
             .args(args)
-            .spawn()
-            .unwrap();
-
-        child
+            .args(&self.compose_content.only_services);
+        self.spawn_recorded(&mut command).unwrap()
     }
     pub fn restart(&mut self) -> Option<Child> {
         let selected = self.compose_content.state.selected()?;
         let key = &self.compose_content.compose.services.0.keys()[selected];
-        self.compose_content
-            .logs
-            .lock()
-            .unwrap()
-            .shift_remove(&selected);
+        self.compose_content.clear_logs(selected);
 
-        let child = Command::new("docker")
-            .args(["compose", "-f", &self.target, "restart", key])
+        let mut command = self.compose_command();
+        command
+            .args(["restart", key])
+            .args(self.timeout_args())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .stdin(Stdio::null())
-            .spawn()
-            .unwrap();
+            .stdin(Stdio::null());
+        let child = self.spawn_recorded(&mut command).unwrap();
 
         Some(child)
     }
 
-    pub async fn refresh(&mut self) -> anyhow::Result<()> {
-        let mut list_container_filters = HashMap::new();
-        list_container_filters.insert("status", vec!["running"]);
+    /// Interrupts every running compose command.
+    pub fn interrupt(&mut self) {
+        match self.running_commands.interrupt_all() {
+            0 => self.set_status(String::from("Nothing to interrupt")),
+            n => {
+                // The cancelled services won't start, so they shouldn't look like they are about to.
+                self.clear_starting();
+                self.set_status(format!("Interrupting {n} command(s)"));
+            }
+        }
+    }
+
+    /// Opens the host directory of the bind mount at the top of the Volumes quadrant in the file manager.
+    ///
+    /// Named and anonymous volumes live inside Docker's storage, so only bind mounts are opened.
+    /// If the mount is a single file, its directory is opened. Returns the opened path.
+    pub fn open_selected_mount(&self) -> anyhow::Result<PathBuf> {
+        let path = self.selected_bind_mount()?;
+        let directory = if path.is_file() {
+            path.parent().unwrap_or(&path)
+        } else {
+            &path
+        };
+        if !directory.is_dir() {
+            anyhow::bail!(
+                "{} doesn't exist on this host, it may be inside the Docker VM",
+                directory.display()
+            );
+        }
+        open::that_detached(directory)
+            .with_context(|| format!("Failed to open {}", directory.display()))?;
+        Ok(directory.to_path_buf())
+    }
+
+    /// Lists the top-level entries of the bind mount at the top of the Volumes quadrant, or hides the listing.
+    ///
+    /// Does nothing for named volumes and for the sources that are not directories on this host.
+    pub fn toggle_mount_listing(&mut self) {
+        let Some(selected) = self.compose_content.state.selected() else {
+            return;
+        };
+        let mount = self.alternate_screen.upper_right_scroll;
+        if let Some((service, listed, _)) = self.alternate_screen.mount_listing.take() {
+            if (service, listed) == (selected, mount) {
+                return;
+            }
+        }
+        let Ok(entries) = self
+            .selected_bind_mount()
+            .and_then(|path| Ok(std::fs::read_dir(path)?))
+        else {
+            return;
+        };
+        let mut entries = entries
+            .filter_map(Result::ok)
+            .map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if entry.file_type().is_ok_and(|typ| typ.is_dir()) {
+                    format!("{name}/")
+                } else {
+                    name
+                }
+            })
+            .collect::<Vec<_>>();
+        entries.sort();
+        self.alternate_screen.mount_listing = Some((selected, mount, entries));
+    }
+
+    /// The host path of the bind mount at the top of the Volumes quadrant.
+    fn selected_bind_mount(&self) -> anyhow::Result<PathBuf> {
+        let selected = self
+            .compose_content
+            .state
+            .selected()
+            .context("No service is selected")?;
+        let mount = self
+            .container_info
+            .get(&selected)
+            .and_then(|info| info.as_ref()?.mounts.as_ref())
+            .and_then(|mounts| mounts.get(self.alternate_screen.upper_right_scroll))
+            .context("The container has no mounts")?;
+        if mount.typ != Some(MountPointTypeEnum::BIND) {
+            anyhow::bail!(
+                "{} is not a bind mount, only host directories can be opened",
+                mount.name.as_deref().unwrap_or("The volume")
+            );
+        }
+        let source = mount.source.as_deref().context("The mount has no source")?;
+        Ok(PathBuf::from(source))
+    }
+
+    /// Copies files between the host and the selected service's container with `docker compose cp`.
+    ///
+    /// The `input` is the source and the destination separated by whitespace, the container path is prefixed
+    /// with a colon, like `:/var/log/app.log ./app.log`. Returns the command and its success message.
+    pub fn copy_files(&mut self, input: &str) -> anyhow::Result<(Child, String)> {
+        let selected = self
+            .compose_content
+            .state
+            .selected()
+            .context("No service is selected")?;
+        let service = &self.compose_content.compose.services.0.keys()[selected];
+        let [source, destination] = input
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Expected a source and a destination, got \"{input}\""))?;
+        anyhow::ensure!(
+            source.starts_with(':') != destination.starts_with(':'),
+            "Exactly one of the paths must be in the container, prefix it with :"
+        );
+        let in_container = |path: &str| match path.strip_prefix(':') {
+            Some(path) => format!("{service}:{path}"),
+            None => path.to_string(),
+        };
+        let (source, destination) = (in_container(source), in_container(destination));
+
+        let mut command = self.compose_command();
+        command
+            .args(["cp", &source, &destination])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdin(Stdio::null());
+        let child = self
+            .spawn_recorded(&mut command)
+            .context("Failed to run docker compose cp")?;
+        Ok((child, format!("Copied {source} to {destination}")))
+    }
+
+    /// Runs `docker compose ps` for the active project and keeps its output for the panel.
+    pub async fn fetch_compose_ps(&mut self) {
+        let output = self
+            .compose_command()
+            .args(["ps", "--all", "--format", "json"])
+            .stdin(Stdio::null())
+            .output()
+            .await;
+        self.compose_ps = match output {
+            Ok(output) if output.status.success() => {
+                Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+            Ok(output) => Err(String::from_utf8_lossy(&output.stderr).into_owned()),
+            Err(e) => Err(e.to_string()),
+        };
+    }
+
+    /// The names of every container compose created for the project, found by their labels.
+    /// The services hidden by the filters are included, but not the ones left out by `--services`.
+    pub fn project_container_names(&self) -> Vec<String> {
+        let only = &self.compose_content.only_services;
+        let mut names = self
+            .labeled_containers
+            .iter()
+            .filter(|((project, service), _)| {
+                *project == self.project_name && (only.is_empty() || only.contains(service))
+            })
+            .map(|(_, name)| name.clone())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// The name of the container compose created for the service, found by its labels.
+    pub fn labeled_container_name(&self, idx: usize) -> Option<&str> {
+        let (service, _) = self.compose_content.compose.services.0.get_index(idx)?;
+        self.labeled_containers
+            .get(&(self.project_name.clone(), service.clone()))
+            .map(String::as_str)
+    }
 
-        let containers = &self
+    /// Fetches the names of the running containers, and the ones that exist but are stopped.
+    pub async fn refresh_container_names(&mut self) -> anyhow::Result<()> {
+        let containers = self
             .docker
-            .list_containers(Some(ListContainersOptions {
+            .list_containers(Some(ListContainersOptions::<String> {
                 all: true,
-                filters: list_container_filters,
                 ..Default::default()
             }))
             .await?;
 
-        self.running_container_names = containers
+        self.labeled_containers = containers
             .iter()
-            .cloned()
-            .filter_map(|c| c.names)
-            .flatten()
-            .map(|name| name.trim_start_matches('/').into())
-            .collect::<Vec<String>>();
+            .filter_map(|c| {
+                let labels = c.labels.as_ref()?;
+                let project = labels.get(PROJECT_LABEL)?;
+                let service = labels.get(SERVICE_LABEL)?;
+                let name = c.names.as_ref()?.first()?.trim_start_matches('/');
+                Some(((project.clone(), service.clone()), name.to_string()))
+            })
+            .collect();
+        let (running, stopped): (Vec<_>, Vec<_>) =
+            project_containers(containers, &self.project_name)
+                .partition(|c| c.state.as_deref() == Some("running"));
+        let names = |containers: Vec<ContainerSummary>| {
+            containers
+                .into_iter()
+                .filter_map(|c| c.names)
+                .flatten()
+                .map(|name| name.trim_start_matches('/').into())
+                .collect::<Vec<String>>()
+        };
+        self.running_container_names = names(running);
+        self.stopped_container_names = names(stopped);
+
+        Ok(())
+    }
+
+    pub async fn refresh(&mut self) -> anyhow::Result<()> {
+        self.refresh_container_names().await?;
         let clear_start =
             self.running_container_names
                 .iter()
@@ -630,6 +3284,7 @@ impl App {
             .names
             .retain(|i, _| clear_stop.contains(i));
 
+        self.ensure_visible_selection();
         self.start_all_log_streaming().await?;
         self.fetch_all_container_info().await?;
 
@@ -637,16 +3292,13 @@ impl App {
     }
 
     // FIXME: Should run prune, not remove
-    pub async fn remove_container(
-        &mut self,
-        v: bool,
-        tx: Sender<DockerEvent>,
-    ) -> anyhow::Result<()> {
+    pub async fn remove_container(&mut self, v: bool) -> anyhow::Result<()> {
         let Some(selected) = self.compose_content.state.selected() else {
             return Ok(());
         };
         let container_name = &self.container_name_mapping[&selected];
-        if let Err(e) = self
+        let audit = self.audit_entry("remove", false);
+        let result = self
             .docker
             .remove_container(
                 container_name,
@@ -656,17 +3308,25 @@ impl App {
                     ..Default::default()
                 }),
             )
-            .await
-        {
-            tx.send(DockerEvent::ErrorLog(e.to_string())).await?;
+            .await;
+        if let Some(audit) = audit {
+            audit.finish(if result.is_ok() {
+                Outcome::Success
+            } else {
+                Outcome::Failure(None)
+            });
         }
-        tx.send(DockerEvent::Refresh).await?;
-
-        Ok(())
+        // Runs on the UI task, so the results are applied right away instead of sent to itself.
+        if let Err(e) = result {
+            self.report_error(e.to_string());
+            self.clear_starting();
+        }
+        self.refresh().await
     }
 
     // FIXME: Should run prune, not remove
-    pub async fn wipe(&mut self, v: bool, tx: Sender<DockerEvent>) -> anyhow::Result<()> {
+    pub async fn wipe(&mut self, v: bool) -> anyhow::Result<()> {
+        let audit = self.audit_entry("wipe", true);
         let result =
             futures::future::join_all(self.container_name_mapping.values().map(|container_name| {
                 let docker = self.docker.clone();
@@ -690,13 +3350,18 @@ impl App {
             .filter_map(|r| r.as_ref().err())
             .map(|e| e.to_string())
             .collect::<Vec<String>>();
+        if let Some(audit) = audit {
+            audit.finish(if errors.is_empty() {
+                Outcome::Success
+            } else {
+                Outcome::Failure(None)
+            });
+        }
         if !errors.is_empty() {
-            tx.send(DockerEvent::ErrorLog(errors.join("\n"))).await?;
+            self.report_error(errors.join("\n"));
+            self.clear_starting();
         }
-
-        tx.send(DockerEvent::Refresh).await?;
-
-        Ok(())
+        self.refresh().await
     }
 
     pub fn clear_starting(&mut self) {
@@ -704,3 +3369,223 @@ impl App {
         self.compose_content.start_queued.names.clear();
     }
 }
+
+/// The dotted namespace of a label, like `com.docker.compose` for `com.docker.compose.project`.
+///
+/// At most three components are kept, and labels without a dot belong to the empty namespace.
+fn label_namespace(name: &str) -> String {
+    let components = name.split('.').collect::<Vec<_>>();
+    let len = components.len().saturating_sub(1).min(3);
+    components[..len].join(".")
+}
+
+/// The containers compose created for the project, by their project label.
+///
+/// Matching by the name alone would count a container of another project, or one started by
+/// hand, that happens to have the same name. The label holds the normalized project name.
+fn project_containers(
+    containers: Vec<ContainerSummary>,
+    project_name: &str,
+) -> impl Iterator<Item = ContainerSummary> {
+    let project_name = normalize_project_name(project_name);
+    containers.into_iter().filter(move |c| {
+        c.labels
+            .as_ref()
+            .and_then(|labels| labels.get(PROJECT_LABEL))
+            .is_some_and(|project| *project == project_name)
+    })
+}
+
+/// Whether the log line mentions an error, a panic or a fatal condition.
+fn looks_like_error(line: &str) -> bool {
+    let line = line.to_ascii_lowercase();
+    ["error", "fatal", "panic", "exception"]
+        .iter()
+        .any(|word| line.contains(word))
+}
+
+/// The value of the label declared for the service, `Some(None)` if it's declared without a value.
+fn service_label(service: &Service, key: &str) -> Option<Option<String>> {
+    match &service.labels {
+        Labels::List(labels) => labels.iter().find_map(|label| match label.split_once('=') {
+            Some((k, v)) => (k == key).then(|| Some(v.to_string())),
+            None => (label == key).then_some(None),
+        }),
+        Labels::Map(labels) => labels.get(key).map(|v| Some(v.clone())),
+    }
+}
+
+/// The length of the longest `depends_on` chain below the service, 0 if it has no dependencies.
+///
+/// `path` holds the services being visited, so dependency cycles end instead of recursing forever.
+fn dependency_depth(compose: &Compose, idx: usize, path: &mut Vec<usize>) -> usize {
+    let services = &compose.services.0;
+    let Some((_, Some(service))) = services.get_index(idx) else {
+        return 0;
+    };
+    let dependencies: Vec<&str> = match &service.depends_on {
+        DependsOnOptions::Simple(names) => names.iter().map(String::as_str).collect(),
+        DependsOnOptions::Conditional(names) => names.keys().map(String::as_str).collect(),
+    };
+    path.push(idx);
+    let mut depth = 0;
+    for dependency in dependencies
+        .into_iter()
+        .filter_map(|name| services.get_index_of(name))
+    {
+        if !path.contains(&dependency) {
+            depth = depth.max(dependency_depth(compose, dependency, path) + 1);
+        }
+    }
+    path.pop();
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container(name: &str, project: &str) -> ContainerSummary {
+        ContainerSummary {
+            names: Some(vec![format!("/{name}")]),
+            labels: Some(HashMap::from([(
+                PROJECT_LABEL.to_string(),
+                project.to_string(),
+            )])),
+            ..Default::default()
+        }
+    }
+
+    /// An app managing the services, without connecting to the daemon.
+    fn app(services: &[&str]) -> App {
+        let yaml = services
+            .iter()
+            .map(|service| format!("  {service}:\n    image: busybox\n"))
+            .collect::<String>();
+        let compose: Compose = serde_yaml::from_str(&format!("services:\n{yaml}")).unwrap();
+        let container_name_mapping = services
+            .iter()
+            .enumerate()
+            .map(|(i, service)| (i, format!("test-{service}-1")))
+            .collect();
+        let project = ProjectFile {
+            project_name: String::from("test"),
+            compose,
+            container_name_mapping,
+            target: String::from("docker-compose.yml"),
+            full_path: PathBuf::from("/test/docker-compose.yml"),
+            dotenv: IndexMap::new(),
+        };
+        let docker = Docker::connect_with_http_defaults().unwrap();
+        App::new(project, docker, String::from("test"))
+    }
+
+    #[test]
+    fn queue_all_stop_queues_every_service() {
+        let mut app = app(&["web", "db", "cache"]);
+        app.queue_all(QueueType::Stop);
+        let stop = &app.compose_content.stop_queued;
+        assert_eq!(stop.state, [0, 1, 2]);
+        assert_eq!(stop.names, app.container_name_mapping);
+        assert!(app.compose_content.start_queued.state.is_empty());
+        assert!(app.compose_content.start_queued.names.is_empty());
+    }
+
+    #[test]
+    fn toggle_modifier_sets_the_flag_of_the_digit() {
+        let expected = [
+            ('1', DockerModifier::BUILD),
+            ('2', DockerModifier::FORCE_RECREATE),
+            ('3', DockerModifier::PULL_ALWAYS),
+            ('4', DockerModifier::ABORT_ON_CONTAINER_FAILURE),
+            ('5', DockerModifier::NO_DEPS),
+            ('6', DockerModifier::REMOVE_ORPHANS),
+            ('7', DockerModifier::RENEW_ANON_VOLUMES),
+        ];
+        let mut app = app(&["web"]);
+        for (key, flag) in expected {
+            app.compose_content.modifiers = DockerModifier::empty();
+            app.toggle_modifier(key);
+            assert_eq!(app.compose_content.modifiers, flag, "key {key}");
+        }
+        app.compose_content.modifiers = DockerModifier::empty();
+        for key in ['0', '8', '9', 'a'] {
+            app.toggle_modifier(key);
+        }
+        assert!(app.compose_content.modifiers.is_empty());
+    }
+
+    #[test]
+    fn modifiers_to_up_args() {
+        assert!(DockerModifier::empty().to_args().is_empty());
+        assert_eq!(
+            (DockerModifier::RENEW_ANON_VOLUMES | DockerModifier::NO_DEPS).to_args(),
+            ["--no-deps", "--renew-anon-volumes"]
+        );
+        assert_eq!(
+            (DockerModifier::BUILD | DockerModifier::PULL_ALWAYS).to_args(),
+            ["--build", "--pull", "always"]
+        );
+        assert_eq!(
+            DockerModifier::all().to_args(),
+            [
+                "--build",
+                "--force-recreate",
+                "--pull",
+                "always",
+                "--abort-on-container-exit",
+                "--no-deps",
+                "--remove-orphans",
+                "--renew-anon-volumes",
+            ]
+        );
+    }
+
+    #[test]
+    fn live_output_is_finished_by_its_command_only() {
+        let mut app = app(&["web"]);
+        app.append_output(1, String::from("Pulling web"));
+        app.append_output(2, String::from("Stopping db"));
+        app.finish_output(2, None);
+        assert_eq!(app.live_output, Some(1));
+        assert!(app.show_popup);
+
+        app.append_output(1, String::from("error: no space left"));
+        app.finish_output(1, Some(String::from("error: no space left")));
+        assert_eq!(app.live_output, None);
+        assert!(app.show_popup);
+        let output = app.compose_content.error_msg.as_deref().unwrap();
+        assert!(output.starts_with("Pulling web\nerror: no space left\n"));
+        assert!(output.ends_with("The command failed."));
+    }
+
+    // Selecting a service starts streaming its logs, which needs the runtime.
+    #[tokio::test]
+    async fn service_filter_without_matches_clears_the_selection() {
+        let mut app = app(&["web", "worker", "db"]);
+        app.compose_content.state.select(Some(2));
+        app.set_service_filter("wkr");
+        assert_eq!(app.visible_services(), [1]);
+        assert_eq!(app.compose_content.state.selected(), Some(1));
+
+        app.set_service_filter("zzz");
+        assert!(app.visible_services().is_empty());
+        assert_eq!(app.compose_content.state.selected(), None);
+
+        app.clear_service_filter();
+        assert_eq!(app.visible_services(), [0, 1, 2]);
+        assert_eq!(app.compose_content.state.selected(), Some(2));
+    }
+
+    #[test]
+    fn project_containers_skips_other_projects() {
+        let containers = vec![container("db", "mystack"), container("db", "other")];
+        let kept = project_containers(containers, "MyStack").collect::<Vec<_>>();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(
+            kept[0].labels.as_ref().unwrap()[PROJECT_LABEL],
+            "mystack".to_string()
+        );
+        assert_eq!(normalize_project_name("_My.Stack 2"), "mystack2");
+    }
+}
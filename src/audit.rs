@@ -0,0 +1,116 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::Write as _,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context as _;
+
+/// The audit trail of the mutating actions, appended to a file as one JSON object per line.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    file: Arc<Mutex<File>>,
+}
+
+impl AuditLog {
+    /// Opens the file for appending, creating it if it doesn't exist.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open the audit file {}", path.display()))?;
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    /// Starts the record of an action, it's written once the action finishes.
+    pub fn entry(
+        &self,
+        project: &str,
+        action: &str,
+        services: Vec<String>,
+        modifiers: Vec<String>,
+    ) -> AuditEntry {
+        AuditEntry {
+            log: self.clone(),
+            project: project.to_string(),
+            action: action.to_string(),
+            services,
+            modifiers,
+            command: None,
+        }
+    }
+
+    fn append(&self, line: &str) -> std::io::Result<()> {
+        let mut file = self.file.lock().unwrap();
+        // A single write per line, so concurrent sessions appending to the same file don't interleave.
+        file.write_all(format!("{line}\n").as_bytes())?;
+        file.flush()
+    }
+}
+
+/// How an audited action ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Success,
+    /// Failed, with the exit code of the command if there's one.
+    Failure(Option<i32>),
+    Cancelled,
+}
+
+/// An action waiting to be written to the audit trail.
+#[derive(Debug)]
+pub struct AuditEntry {
+    log: AuditLog,
+    project: String,
+    action: String,
+    services: Vec<String>,
+    modifiers: Vec<String>,
+    /// The compose command the action ran, if it ran one.
+    command: Option<String>,
+}
+
+impl AuditEntry {
+    /// Records the command line the action ran.
+    pub fn with_command(mut self, command: Option<String>) -> Self {
+        self.command = command;
+        self
+    }
+
+    /// Writes the action with its outcome.
+    ///
+    /// Called from background tasks that have nowhere to report to, so failing to write is ignored.
+    pub fn finish(self, outcome: Outcome) {
+        let (status, exit_code) = match outcome {
+            Outcome::Success => ("success", Some(0)),
+            Outcome::Failure(exit_code) => ("failure", exit_code),
+            Outcome::Cancelled => ("cancelled", None),
+        };
+        let line = serde_json::json!({
+            "timestamp": jiff::Timestamp::now().to_string(),
+            "project": self.project,
+            "action": self.action,
+            "services": self.services,
+            "modifiers": self.modifiers,
+            "command": self.command,
+            "status": status,
+            "exit_code": exit_code,
+        });
+        self.log.append(&line.to_string()).ok();
+    }
+
+    /// Writes the action with the outcome of the exited command.
+    pub fn finish_command(self, status: std::process::ExitStatus, interrupted: bool) {
+        let outcome = if interrupted {
+            Outcome::Cancelled
+        } else if status.success() {
+            Outcome::Success
+        } else {
+            Outcome::Failure(status.code())
+        };
+        self.finish(outcome);
+    }
+}
@@ -0,0 +1,52 @@
+//! The bookmarked services of each project, kept in the state directory across sessions.
+//!
+//! Services are stored by name, so the bookmarks survive reordering the compose file.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use anyhow::Context as _;
+
+use crate::recent::state_dir;
+
+/// The names of the bookmarked services by the project name, in the order they were bookmarked.
+pub type Bookmarks = BTreeMap<String, Vec<String>>;
+
+fn bookmarks_file() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join("bookmarks"))
+}
+
+/// The saved bookmarks, one `project<TAB>service,service` line per project.
+pub fn load() -> Bookmarks {
+    let Some(Ok(content)) = bookmarks_file().map(std::fs::read_to_string) else {
+        return Bookmarks::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(project, services)| {
+            let services = services
+                .split(',')
+                .filter(|service| !service.is_empty())
+                .map(String::from)
+                .collect();
+            (project.to_string(), services)
+        })
+        .collect()
+}
+
+pub fn save(bookmarks: &Bookmarks) -> anyhow::Result<()> {
+    let Some(bookmarks_file) = bookmarks_file() else {
+        return Ok(());
+    };
+    if let Some(dir) = bookmarks_file.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create the state directory {}", dir.display()))?;
+    }
+    let content = bookmarks
+        .iter()
+        .filter(|(_, services)| !services.is_empty())
+        .map(|(project, services)| format!("{project}\t{}\n", services.join(",")))
+        .collect::<String>();
+    std::fs::write(&bookmarks_file, content)
+        .with_context(|| format!("Failed to write {}", bookmarks_file.display()))
+}
@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use bollard::{Docker, API_DEFAULT_VERSION};
+
+/// The timeout of the Docker API requests in seconds, same as bollard's default.
+const TIMEOUT: u64 = 120;
+
+/// The directory of the Docker CLI configuration, respecting `DOCKER_CONFIG`.
+fn docker_config_dir() -> Option<PathBuf> {
+    std::env::var_os("DOCKER_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".docker")))
+}
+
+/// Reads the Docker endpoint of the context from the Docker CLI configuration.
+///
+/// The contexts are stored in `contexts/meta/<hash>/meta.json`, we look for the one with the matching name.
+pub fn context_endpoint(name: &str) -> anyhow::Result<String> {
+    let meta_dir = docker_config_dir()
+        .context("Failed to locate the Docker CLI configuration directory")?
+        .join("contexts")
+        .join("meta");
+    let entries = std::fs::read_dir(&meta_dir)
+        .with_context(|| format!("Failed to read Docker contexts from {}", meta_dir.display()))?;
+
+    for entry in entries.filter_map(Result::ok) {
+        let Ok(payload) = std::fs::read_to_string(entry.path().join("meta.json")) else {
+            continue;
+        };
+        // JSON is valid YAML, so we don't need another parser for this.
+        let Ok(meta) = serde_yaml::from_str::<serde_yaml::Value>(&payload) else {
+            continue;
+        };
+        if meta["Name"].as_str() != Some(name) {
+            continue;
+        }
+        return meta["Endpoints"]["docker"]["Host"]
+            .as_str()
+            .map(String::from)
+            .with_context(|| format!("Docker context '{name}' has no Docker endpoint"));
+    }
+
+    anyhow::bail!("Docker context '{name}' not found")
+}
+
+/// Connects to the Docker daemon of the given context, or with the socket defaults if there's none.
+pub fn connect(context: Option<&str>) -> anyhow::Result<Docker> {
+    let docker = match context {
+        None | Some("default") => Docker::connect_with_socket_defaults()?,
+        Some(name) => {
+            let endpoint = context_endpoint(name)?;
+            if let Some(path) = endpoint.strip_prefix("unix://") {
+                Docker::connect_with_socket(path, TIMEOUT, API_DEFAULT_VERSION)?
+            } else if endpoint.starts_with("tcp://") || endpoint.starts_with("http://") {
+                Docker::connect_with_http(&endpoint, TIMEOUT, API_DEFAULT_VERSION)?
+            } else {
+                anyhow::bail!("Unsupported endpoint '{endpoint}' for Docker context '{name}'")
+            }
+        }
+    };
+    Ok(docker)
+}
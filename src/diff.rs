@@ -0,0 +1,296 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use bollard::secret::ContainerInspectResponse;
+use docker_compose_types::{Environment, Ports, PublishedPort, Service, Volumes};
+
+use crate::utils::interpolate;
+
+/// A single difference between the compose file and a running container.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Drift {
+    /// Present in both, with the same value.
+    Unchanged(String),
+    /// Only present in the running container.
+    Added(String),
+    /// Only present in the compose file.
+    Removed(String),
+    /// Present in both, but with different values.
+    Changed {
+        name: String,
+        file: String,
+        runtime: String,
+    },
+}
+
+impl Drift {
+    pub fn is_drift(&self) -> bool {
+        !matches!(self, Drift::Unchanged(_))
+    }
+}
+
+/// The differences between a service's compose definition and its running container.
+#[derive(Debug, Default)]
+pub struct ServiceDiff {
+    pub image: Vec<Drift>,
+    pub env: Vec<Drift>,
+    pub ports: Vec<Drift>,
+    pub volumes: Vec<Drift>,
+}
+
+impl ServiceDiff {
+    pub fn new(service: &Service, info: &ContainerInspectResponse) -> Self {
+        Self {
+            image: diff_image(service, info),
+            env: diff_env(service, info),
+            ports: diff_sets(compose_ports(service), runtime_ports(info)),
+            volumes: diff_sets(compose_volumes(service), runtime_volumes(info)),
+        }
+    }
+
+    pub fn has_drift(&self) -> bool {
+        [&self.image, &self.env, &self.ports, &self.volumes]
+            .iter()
+            .any(|section| section.iter().any(Drift::is_drift))
+    }
+}
+
+fn diff_image(service: &Service, info: &ContainerInspectResponse) -> Vec<Drift> {
+    let Some(file) = service.image.as_deref().map(interpolate) else {
+        return vec![Drift::Unchanged(String::from("built from source"))];
+    };
+    let runtime = info
+        .config
+        .as_ref()
+        .and_then(|c| c.image.clone())
+        .unwrap_or_default();
+    if file == runtime {
+        vec![Drift::Unchanged(file)]
+    } else {
+        vec![Drift::Changed {
+            name: String::from("image"),
+            file,
+            runtime,
+        }]
+    }
+}
+
+/// The environment declared in the compose file, with values interpolated.
+pub fn compose_env(service: &Service) -> Vec<(String, String)> {
+    match &service.environment {
+        Environment::List(list) => list
+            .iter()
+            .map(|entry| match entry.split_once('=') {
+                Some((k, v)) => (k.to_string(), interpolate(v)),
+                None => (entry.clone(), std::env::var(entry).unwrap_or_default()),
+            })
+            .collect(),
+        Environment::KvPair(map) => map
+            .iter()
+            .map(|(k, v)| {
+                let value = match v {
+                    Some(v) => interpolate(&v.to_string()),
+                    None => std::env::var(k).unwrap_or_default(),
+                };
+                (k.clone(), value)
+            })
+            .collect(),
+    }
+}
+
+fn diff_env(service: &Service, info: &ContainerInspectResponse) -> Vec<Drift> {
+    let runtime = info
+        .config
+        .as_ref()
+        .and_then(|c| c.env.as_deref())
+        .unwrap_or_default();
+    // Variables only present in the container usually come from the image, so those are not reported.
+    compose_env(service)
+        .into_iter()
+        .map(|(name, file)| {
+            let runtime = runtime.iter().find_map(|entry| {
+                entry
+                    .split_once('=')
+                    .filter(|(k, _)| *k == name)
+                    .map(|(_, v)| v.to_string())
+            });
+            match runtime {
+                Some(runtime) if runtime == file => Drift::Unchanged(format!("{name}={file}")),
+                Some(runtime) => Drift::Changed {
+                    name,
+                    file,
+                    runtime,
+                },
+                None => Drift::Removed(format!("{name}={file}")),
+            }
+        })
+        .collect()
+}
+
+/// Normalizes a port to `host_port:container_port/protocol`, or `container_port/protocol` if it's not published.
+fn normalize_port(host_port: Option<&str>, container_port: &str, protocol: &str) -> String {
+    match host_port {
+        Some(host_port) if !host_port.is_empty() => {
+            format!("{host_port}:{container_port}/{protocol}")
+        }
+        _ => format!("{container_port}/{protocol}"),
+    }
+}
+
+/// A container port that the image exposes, the compose file publishes, or both.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortExposure {
+    /// The container port, like `80/tcp`.
+    pub port: String,
+    /// Whether the image declares the port with `EXPOSE`.
+    pub exposed: bool,
+    /// The host ports the compose file publishes the port on.
+    pub published: Vec<String>,
+}
+
+impl PortExposure {
+    /// Whether the port is only exposed or only published, which is often a mistake.
+    pub fn is_mismatch(&self) -> bool {
+        !self.exposed || self.published.is_empty()
+    }
+}
+
+/// Pairs the ports exposed by the image with the ports published in the compose file, ordered by port.
+pub fn port_exposure(service: &Service, info: &ContainerInspectResponse) -> Vec<PortExposure> {
+    let mut ports: BTreeMap<String, PortExposure> = BTreeMap::new();
+    let exposed = info
+        .config
+        .as_ref()
+        .and_then(|config| config.exposed_ports.as_ref())
+        .map(|ports| ports.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    for port in exposed {
+        ports.insert(
+            port.clone(),
+            PortExposure {
+                port,
+                exposed: true,
+                published: Vec::new(),
+            },
+        );
+    }
+    for mapping in compose_ports(service) {
+        let (host_port, container_port) = match mapping.split_once(':') {
+            Some((host_port, container_port)) => (Some(host_port), container_port),
+            None => (None, mapping.as_str()),
+        };
+        let entry = ports
+            .entry(container_port.to_string())
+            .or_insert_with(|| PortExposure {
+                port: container_port.to_string(),
+                exposed: false,
+                published: Vec::new(),
+            });
+        entry
+            .published
+            .push(host_port.unwrap_or("random").to_string());
+    }
+    ports.into_values().collect()
+}
+
+/// The host ports the compose file publishes the service on, like `8080/tcp`.
+pub fn host_ports(service: &Service) -> Vec<String> {
+    compose_ports(service)
+        .into_iter()
+        .filter_map(|mapping| {
+            let (host_port, container_port) = mapping.split_once(':')?;
+            let protocol = container_port.split_once('/').map_or("tcp", |(_, p)| p);
+            Some(format!("{host_port}/{protocol}"))
+        })
+        .collect()
+}
+
+fn compose_ports(service: &Service) -> BTreeSet<String> {
+    match &service.ports {
+        Ports::Short(ports) => ports
+            .iter()
+            .map(|port| {
+                let port = interpolate(port);
+                let (mapping, protocol) = port.split_once('/').unwrap_or((&port, "tcp"));
+                let parts = mapping.rsplitn(3, ':').collect::<Vec<_>>();
+                normalize_port(parts.get(1).copied(), parts[0], protocol)
+            })
+            .collect(),
+        Ports::Long(ports) => ports
+            .iter()
+            .map(|port| {
+                let published = port.published.as_ref().map(|p| match p {
+                    PublishedPort::Single(p) => p.to_string(),
+                    PublishedPort::Range(r) => r.clone(),
+                });
+                normalize_port(
+                    published.as_deref(),
+                    &port.target.to_string(),
+                    port.protocol.as_deref().unwrap_or("tcp"),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn runtime_ports(info: &ContainerInspectResponse) -> BTreeSet<String> {
+    let Some(bindings) = info
+        .host_config
+        .as_ref()
+        .and_then(|c| c.port_bindings.as_ref())
+    else {
+        return BTreeSet::new();
+    };
+    bindings
+        .iter()
+        .flat_map(|(port, bindings)| {
+            let (container_port, protocol) = port.split_once('/').unwrap_or((port, "tcp"));
+            match bindings {
+                Some(bindings) if !bindings.is_empty() => bindings
+                    .iter()
+                    .map(|b| normalize_port(b.host_port.as_deref(), container_port, protocol))
+                    .collect::<Vec<_>>(),
+                _ => vec![normalize_port(None, container_port, protocol)],
+            }
+        })
+        .collect()
+}
+
+fn compose_volumes(service: &Service) -> BTreeSet<String> {
+    service
+        .volumes
+        .iter()
+        .filter_map(|volume| match volume {
+            Volumes::Simple(volume) => {
+                let volume = interpolate(volume);
+                let mut parts = volume.split(':');
+                match (parts.next(), parts.next()) {
+                    (Some(_), Some(target)) => Some(target.to_string()),
+                    (Some(target), None) => Some(target.to_string()),
+                    _ => None,
+                }
+            }
+            Volumes::Advanced(volume) => Some(volume.target.clone()),
+        })
+        .collect()
+}
+
+fn runtime_volumes(info: &ContainerInspectResponse) -> BTreeSet<String> {
+    info.mounts
+        .iter()
+        .flatten()
+        .filter_map(|mount| mount.destination.clone())
+        .collect()
+}
+
+fn diff_sets(file: BTreeSet<String>, runtime: BTreeSet<String>) -> Vec<Drift> {
+    let mut result = file
+        .union(&runtime)
+        .map(|item| match (file.contains(item), runtime.contains(item)) {
+            (true, true) => Drift::Unchanged(item.clone()),
+            (true, false) => Drift::Removed(item.clone()),
+            _ => Drift::Added(item.clone()),
+        })
+        .collect::<Vec<_>>();
+    result.sort_by_key(|d| !d.is_drift());
+    result
+}
@@ -0,0 +1,93 @@
+use docker_compose_types::{Environment, Service};
+use indexmap::IndexMap;
+
+use crate::utils::interpolate_with;
+
+/// Where the value of an environment variable comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnvSource {
+    /// Written literally in the compose file.
+    ComposeFile,
+    /// The referenced variable is set in the `.env` file.
+    DotEnv(String),
+    /// The referenced variable is set in the shell, overriding the `.env` file if it's set there too.
+    Shell(String),
+    /// The referenced variable is not set anywhere.
+    Unset(String),
+}
+
+impl std::fmt::Display for EnvSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvSource::ComposeFile => write!(f, "compose file"),
+            EnvSource::DotEnv(name) => write!(f, "${name} from .env"),
+            EnvSource::Shell(name) => write!(f, "${name} from shell"),
+            EnvSource::Unset(name) => write!(f, "${name} is unset"),
+        }
+    }
+}
+
+/// An environment variable of a service as dcr understands it.
+#[derive(Debug, Clone)]
+pub struct EnvEntry {
+    pub name: String,
+    pub value: String,
+    pub sources: Vec<EnvSource>,
+}
+
+/// Resolves the service's declared environment against the `.env` file and the shell.
+pub fn effective_env(service: &Service, dotenv: &IndexMap<String, String>) -> Vec<EnvEntry> {
+    let resolve = |name: &str, sources: &mut Vec<EnvSource>| {
+        let shell = std::env::var(name).ok();
+        let dot = dotenv.get(name);
+        match (shell, dot) {
+            // The `.env` file was loaded into our own environment, so an equal value means it came from there.
+            (Some(shell), Some(dot)) if shell == *dot => {
+                sources.push(EnvSource::DotEnv(name.to_string()));
+                Some(shell)
+            }
+            (Some(shell), _) => {
+                sources.push(EnvSource::Shell(name.to_string()));
+                Some(shell)
+            }
+            (None, Some(dot)) => {
+                sources.push(EnvSource::DotEnv(name.to_string()));
+                Some(dot.clone())
+            }
+            (None, None) => {
+                sources.push(EnvSource::Unset(name.to_string()));
+                None
+            }
+        }
+    };
+    let entry = |name: &str, value: Option<String>| {
+        let mut sources = Vec::new();
+        let value = match value {
+            Some(value) => interpolate_with(&value, |var| resolve(var, &mut sources)),
+            // A variable without a value is passed through from the environment.
+            None => resolve(name, &mut sources).unwrap_or_default(),
+        };
+        if sources.is_empty() {
+            sources.push(EnvSource::ComposeFile);
+        }
+        EnvEntry {
+            name: name.to_string(),
+            value,
+            sources,
+        }
+    };
+
+    match &service.environment {
+        Environment::List(list) => list
+            .iter()
+            .map(|item| match item.split_once('=') {
+                Some((name, value)) => entry(name, Some(value.to_string())),
+                None => entry(item, None),
+            })
+            .collect(),
+        Environment::KvPair(map) => map
+            .iter()
+            .map(|(name, value)| entry(name, value.as_ref().map(|v| v.to_string())))
+            .collect(),
+    }
+}
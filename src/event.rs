@@ -87,7 +87,7 @@ impl EventHandler {
         self.receiver
             .recv()
             .await
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "This is an IO error"))
+            .ok_or_else(|| std::io::Error::other("This is an IO error"))
             .map_err(Into::into)
     }
 }
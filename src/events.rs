@@ -0,0 +1,168 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use bollard::system::EventsOptions;
+use futures::StreamExt;
+use tokio::task::JoinHandle;
+
+/// The number of lifecycle events kept.
+pub const EVENTS_LEN: usize = 1_000;
+
+/// The container actions that change the lifecycle, the rest like `exec_start` or `attach` are noise.
+const LIFECYCLE_ACTIONS: [&str; 11] = [
+    "create",
+    "start",
+    "restart",
+    "stop",
+    "kill",
+    "die",
+    "oom",
+    "pause",
+    "unpause",
+    "destroy",
+    "health_status",
+];
+
+/// A lifecycle event of a container of the project.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectEvent {
+    pub time: jiff::Timestamp,
+    /// The compose service of the container.
+    pub service: String,
+    /// The action, like `die (exit code 1)` or `health_status: healthy`.
+    pub action: String,
+}
+
+/// An event of the project as Docker reports it, for any kind of object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawEvent {
+    pub time: jiff::Timestamp,
+    /// The type of the object, like `container` or `network`.
+    pub kind: String,
+    pub action: String,
+    /// The ID of the object, shortened like `docker ps` does.
+    pub actor: String,
+    /// The attributes of the object, sorted by their key.
+    pub attributes: Vec<(String, String)>,
+}
+
+/// The recent events of the active project, capped at [`EVENTS_LEN`].
+#[derive(Debug, Clone)]
+pub struct EventFeed<T = ProjectEvent>(Arc<Mutex<VecDeque<T>>>);
+
+impl<T> Default for EventFeed<T> {
+    fn default() -> Self {
+        Self(Arc::default())
+    }
+}
+
+impl<T: Clone> EventFeed<T> {
+    pub fn push(&self, event: T) {
+        let mut events = self.0.lock().unwrap();
+        if events.len() == EVENTS_LEN {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// The events, the oldest first.
+    pub fn events(&self) -> Vec<T> {
+        self.0.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+/// The filter of the events of the compose project.
+fn project_filter(project_name: &str) -> (String, Vec<String>) {
+    (
+        String::from("label"),
+        vec![format!("com.docker.compose.project={project_name}")],
+    )
+}
+
+/// Streams the lifecycle events of the containers of the compose project into the feed.
+pub fn spawn_event_stream(
+    project_name: &str,
+    docker: &bollard::Docker,
+    feed: EventFeed,
+) -> JoinHandle<()> {
+    let filters = HashMap::from([
+        (String::from("type"), vec![String::from("container")]),
+        project_filter(project_name),
+    ]);
+    let mut stream = docker.events(Some(EventsOptions::<String> {
+        filters,
+        ..Default::default()
+    }));
+    tokio::spawn(async move {
+        while let Some(Ok(message)) = stream.next().await {
+            let Some(action) = message.action else {
+                continue;
+            };
+            let kind = action.split(':').next().unwrap_or_default();
+            if !LIFECYCLE_ACTIONS.contains(&kind) {
+                continue;
+            }
+            let attributes = message
+                .actor
+                .and_then(|actor| actor.attributes)
+                .unwrap_or_default();
+            let Some(service) = attributes.get("com.docker.compose.service") else {
+                continue;
+            };
+            let action = match attributes.get("exitCode") {
+                Some(code) if kind == "die" => format!("{action} (exit code {code})"),
+                _ => action,
+            };
+            let time = message
+                .time_nano
+                .and_then(|nanos| jiff::Timestamp::from_nanosecond(nanos as i128).ok())
+                .unwrap_or_else(jiff::Timestamp::now);
+            feed.push(ProjectEvent {
+                time,
+                service: service.clone(),
+                action,
+            });
+        }
+    })
+}
+
+/// Streams every event of the compose project into the feed unfiltered, the containers, the
+/// networks and the volumes alike.
+pub fn spawn_raw_event_stream(
+    project_name: &str,
+    docker: &bollard::Docker,
+    feed: EventFeed<RawEvent>,
+) -> JoinHandle<()> {
+    let mut stream = docker.events(Some(EventsOptions::<String> {
+        filters: HashMap::from([project_filter(project_name)]),
+        ..Default::default()
+    }));
+    tokio::spawn(async move {
+        while let Some(Ok(message)) = stream.next().await {
+            let time = message
+                .time_nano
+                .and_then(|nanos| jiff::Timestamp::from_nanosecond(nanos as i128).ok())
+                .unwrap_or_else(jiff::Timestamp::now);
+            let actor = message.actor.unwrap_or_default();
+            let mut attributes = actor
+                .attributes
+                .unwrap_or_default()
+                .into_iter()
+                .collect::<Vec<_>>();
+            attributes.sort();
+            feed.push(RawEvent {
+                time,
+                kind: message.typ.map(|typ| typ.to_string()).unwrap_or_default(),
+                action: message.action.unwrap_or_default(),
+                actor: actor.id.unwrap_or_default().chars().take(12).collect(),
+                attributes,
+            });
+        }
+    })
+}
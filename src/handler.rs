@@ -1,11 +1,72 @@
-use crate::app::App;
+use crate::{
+    app::{App, CommandLine, FocusOrder},
+    audit::{AuditEntry, Outcome},
+};
+use bollard::container::RemoveContainerOptions;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
-use tokio::sync::mpsc::Sender;
+use tokio::{
+    io::{AsyncBufReadExt as _, AsyncRead, BufReader},
+    process::Child,
+    sync::mpsc::{Sender, UnboundedSender},
+};
+
+/// How long after a successful start the started containers are checked for crashes.
+const CRASH_CHECK_DELAY: std::time::Duration = std::time::Duration::from_secs(3);
 
 #[derive(Debug)]
 pub enum DockerEvent {
     Refresh,
     ErrorLog(String),
+    /// A spawned compose command finished.
+    Finished,
+    /// A step of a longer operation, shown as a status message.
+    Progress(String),
+    /// The command streaming its output finished, holds its id and its errors if it failed.
+    OutputFinished(u32, Option<String>),
+    /// A compose command failed, it can be retried.
+    CommandFailed(CommandLine),
+    /// A compose command succeeded, so it doesn't need a retry anymore.
+    CommandSucceeded(CommandLine),
+    /// A while after a successful start, holds the names of the started containers to check
+    /// whether they crashed meanwhile.
+    CheckStarted(Vec<String>),
+}
+
+/// An action waiting for the user's confirmation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Confirm {
+    /// Stop, remove and start the service again, holds the name of the service.
+    Reset(String),
+    /// Quit and run `docker compose down` before exiting.
+    QuitAndDown,
+    /// Remove or tear down containers, holds the names of the affected containers.
+    Destructive(DestructiveAction, Vec<String>),
+}
+
+impl Confirm {
+    pub fn prompt(&self) -> String {
+        match self {
+            Confirm::Reset(service) => format!(
+                "Reset {service}? Its container will be stopped, removed and created again.\n\n\
+                 (y) reset, (v) reset and remove its volumes too, any other key cancels."
+            ),
+            Confirm::QuitAndDown => String::from(
+                "Quit and run docker compose down? The containers of the managed services will \
+                 be stopped and removed.\n\n(y) quit and tear down, (n) just quit, any other key cancels.",
+            ),
+            Confirm::Destructive(action, containers) => {
+                let question = match action {
+                    DestructiveAction::Remove => "Remove the container with its volumes?",
+                    DestructiveAction::Wipe => "Remove all containers with their volumes?",
+                    DestructiveAction::DownAll => "Stop and remove all containers?",
+                };
+                format!(
+                    "{question}\n\nAffected: {}\n\n(y) proceed, any other key cancels.",
+                    containers.join(", ")
+                )
+            }
+        }
+    }
 }
 
 pub enum QueueType {
@@ -17,9 +78,45 @@ pub enum QueueType {
 pub enum AlternateScreenContent {
     Help,
     ContainerDetails(SplitScreen),
+    Diff,
+    Environment,
+    Resources,
+    ComposePs,
+    Dashboard,
+    Build,
+    Diagnostics,
+    Names,
+    Activity,
+    DaemonEvents,
+    FileInfo,
+    Dependencies,
+    Grep,
+    Logs,
     None,
 }
 
+impl AlternateScreenContent {
+    /// Whether the screen is a single scrollable panel.
+    pub fn is_panel(&self) -> bool {
+        matches!(
+            self,
+            AlternateScreenContent::Diff
+                | AlternateScreenContent::Environment
+                | AlternateScreenContent::Resources
+                | AlternateScreenContent::ComposePs
+                | AlternateScreenContent::Dashboard
+                | AlternateScreenContent::Build
+                | AlternateScreenContent::Diagnostics
+                | AlternateScreenContent::Names
+                | AlternateScreenContent::Activity
+                | AlternateScreenContent::DaemonEvents
+                | AlternateScreenContent::FileInfo
+                | AlternateScreenContent::Dependencies
+                | AlternateScreenContent::Grep
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SplitScreen {
     UpperLeft,
@@ -29,20 +126,109 @@ pub enum SplitScreen {
 }
 
 impl SplitScreen {
-    fn transition(self) -> Self {
-        match self {
-            SplitScreen::UpperLeft => SplitScreen::LowerLeft,
-            SplitScreen::LowerLeft => SplitScreen::UpperRight,
-            SplitScreen::UpperRight => SplitScreen::LowerRight,
-            SplitScreen::LowerRight => SplitScreen::UpperLeft,
+    /// The quadrants column by column, the default order of the focus cycling, which is also their
+    /// order from top to bottom when they're stacked on narrow terminals.
+    pub const ALL: [SplitScreen; 4] = [
+        SplitScreen::UpperLeft,
+        SplitScreen::LowerLeft,
+        SplitScreen::UpperRight,
+        SplitScreen::LowerRight,
+    ];
+
+    /// The quadrants in the order the focus moves between them.
+    fn cycle(order: FocusOrder) -> [SplitScreen; 4] {
+        match order {
+            FocusOrder::Columns => Self::ALL,
+            FocusOrder::Rows => [
+                SplitScreen::UpperLeft,
+                SplitScreen::UpperRight,
+                SplitScreen::LowerLeft,
+                SplitScreen::LowerRight,
+            ],
         }
     }
-    fn transition_back(self) -> Self {
+
+    fn transition(self, order: FocusOrder) -> Self {
+        let cycle = Self::cycle(order);
+        let position = cycle.iter().position(|quadrant| *quadrant == self);
+        cycle[position.map_or(0, |i| (i + 1) % cycle.len())]
+    }
+    fn transition_back(self, order: FocusOrder) -> Self {
+        let cycle = Self::cycle(order);
+        let position = cycle.iter().position(|quadrant| *quadrant == self);
+        cycle[position.map_or(0, |i| (i + cycle.len() - 1) % cycle.len())]
+    }
+}
+
+/// A single line text input, submitted with `Enter` and cancelled with `Esc`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub input: String,
+    /// The containers affected by submitting the prompt, listed above the input.
+    pub affected: Vec<String>,
+}
+
+impl Prompt {
+    pub fn new(kind: PromptKind) -> Self {
+        Self {
+            kind,
+            input: String::new(),
+            affected: Vec::new(),
+        }
+    }
+}
+
+/// What the text of the [`Prompt`] is used for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    /// The relative time window of the selected service's logs.
+    Since,
+    /// The label selector filtering the service list.
+    LabelSelector,
+    /// The source and destination of copying files to or from the selected service's container.
+    Copy,
+    /// The project name confirming a destructive action in safe mode.
+    Destructive(DestructiveAction),
+    /// The text searched in the logs, highlighted while it's typed.
+    Search,
+    /// The text searched in the logs of every service.
+    Grep,
+    /// The fuzzy query filtering the service list, applied while it's typed.
+    ServiceFilter,
+}
+
+/// The actions that require typing the project name in safe mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DestructiveAction {
+    /// Removing the selected service's container with its volumes.
+    Remove,
+    /// Removing every container of the project with their volumes.
+    Wipe,
+    /// Stopping and removing every container of the project.
+    DownAll,
+}
+
+impl PromptKind {
+    pub fn title(&self) -> &'static str {
         match self {
-            SplitScreen::LowerLeft => SplitScreen::UpperLeft,
-            SplitScreen::UpperRight => SplitScreen::LowerLeft,
-            SplitScreen::LowerRight => SplitScreen::UpperRight,
-            SplitScreen::UpperLeft => SplitScreen::LowerRight,
+            PromptKind::Since => "Show logs since (e.g. 5m, 1h30m ago), empty to reset",
+            PromptKind::LabelSelector => {
+                "Filter by labels (e.g. tier=frontend,debug), empty to reset"
+            }
+            PromptKind::Copy => "Copy <source> <destination>, prefix container paths with :",
+            PromptKind::Destructive(DestructiveAction::Remove) => {
+                "Type the project name to remove the container with its volumes"
+            }
+            PromptKind::Destructive(DestructiveAction::Wipe) => {
+                "Type the project name to remove all containers with their volumes"
+            }
+            PromptKind::Destructive(DestructiveAction::DownAll) => {
+                "Type the project name to stop and remove all containers"
+            }
+            PromptKind::Search => "Search the logs (case-insensitive), empty to reset",
+            PromptKind::Grep => "Search the logs of every service (case-insensitive)",
+            PromptKind::ServiceFilter => "Filter the services by name (fuzzy), Esc to reset",
         }
     }
 }
@@ -53,30 +239,100 @@ pub async fn handle_key_events(
     app: &mut App,
     tx: Sender<DockerEvent>,
 ) -> anyhow::Result<()> {
+    // Interacting with the popup keeps it open, so it can be read.
+    app.popup_deadline = None;
+
+    if let Some(confirm) = app.confirm.take() {
+        // Any other key cancels the action.
+        match (confirm, key_event.code) {
+            (Confirm::Reset(_), KeyCode::Char(c @ ('y' | 'v'))) => {
+                app.clear_latest_error_log();
+                spawn_reset(app, c == 'v', tx);
+            }
+            (Confirm::QuitAndDown, KeyCode::Char(c @ ('y' | 'n'))) => {
+                app.quit_with_down = c == 'y';
+                app.quit();
+            }
+            (Confirm::Destructive(action, _), KeyCode::Char('y')) => {
+                run_destructive(app, action, tx).await?;
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if let Some(prompt) = &mut app.prompt {
+        let searching = prompt.kind == PromptKind::Search;
+        let filtering = prompt.kind == PromptKind::ServiceFilter;
+        match key_event.code {
+            KeyCode::Esc => {
+                app.prompt = None;
+                if searching {
+                    app.clear_search();
+                }
+                if filtering {
+                    app.clear_service_filter();
+                }
+                return Ok(());
+            }
+            KeyCode::Backspace => {
+                prompt.input.pop();
+            }
+            KeyCode::Char(c) => prompt.input.push(c),
+            KeyCode::Enter => {
+                if let Some(prompt) = app.prompt.take() {
+                    submit_prompt(app, prompt, tx).await;
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
+        // The matches are highlighted as the query is typed.
+        let input = prompt.input.clone();
+        if searching {
+            app.set_search_query(&input);
+        }
+        // The list narrows down as the query is typed.
+        if filtering {
+            app.set_service_filter(&input);
+        }
+        return Ok(());
+    }
+
     match key_event.code {
         // Exit application on `ESC` or `q`
         KeyCode::Esc | KeyCode::Char('q') => {
-            match app.alternate_screen_content {
-                AlternateScreenContent::Help | AlternateScreenContent::ContainerDetails(_) => {
-                    app.alternate_screen_content = AlternateScreenContent::None;
-                    return Ok(());
-                }
-                e @ AlternateScreenContent::None => e,
-            };
+            if app.alternate_screen_content != AlternateScreenContent::None {
+                app.alternate_screen_content = AlternateScreenContent::None;
+                return Ok(());
+            }
             if app.show_popup {
                 app.show_popup = false;
                 app.reset_popup_scroll();
+            } else if app.search_query.is_some() {
+                app.clear_search();
+            } else if app.service_filter.is_some() {
+                app.clear_service_filter();
+            } else if app.down_on_exit {
+                app.confirm = Some(Confirm::QuitAndDown);
             } else {
                 app.quit();
             }
         }
         // Exit application on `Ctrl-C`
-        KeyCode::Char('c') | KeyCode::Char('C') => {
-            if key_event.modifiers == KeyModifiers::CONTROL {
-                app.quit();
-            }
+        KeyCode::Char('c') | KeyCode::Char('C') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.quit();
         }
 
+        KeyCode::Up if app.alternate_screen_content == AlternateScreenContent::Grep => {
+            app.move_grep_selection(-1);
+        }
+        KeyCode::Down if app.alternate_screen_content == AlternateScreenContent::Grep => {
+            app.move_grep_selection(1);
+        }
+        KeyCode::Enter if app.alternate_screen_content == AlternateScreenContent::Grep => {
+            app.open_grep_match();
+        }
         KeyCode::Up => {
             if key_event.modifiers == KeyModifiers::SHIFT {
                 app.up_first(tx.clone());
@@ -95,14 +351,86 @@ pub async fn handle_key_events(
             app.reset_scroll();
         }
 
+        KeyCode::Char('n') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.toggle_only_since_marker();
+        }
+        KeyCode::Char('n') => app.jump_to_search_match(true),
+        KeyCode::Char('#') => app.toggle_line_numbers(),
+        KeyCode::Char('f') if key_event.modifiers == KeyModifiers::CONTROL => {
+            let mut prompt = Prompt::new(PromptKind::ServiceFilter);
+            prompt.input = app.service_filter.clone().unwrap_or_default();
+            app.prompt = Some(prompt);
+        }
+        KeyCode::Char('?') => {
+            let mut prompt = Prompt::new(PromptKind::Grep);
+            prompt.input = app.grep_query.clone();
+            app.prompt = Some(prompt);
+        }
+        KeyCode::Char('/') => {
+            let mut prompt = Prompt::new(PromptKind::Search);
+            prompt.input = app.search_query.clone().unwrap_or_default();
+            app.prompt = Some(prompt);
+        }
+        KeyCode::Char('y') if key_event.modifiers == KeyModifiers::CONTROL => {
+            toggle_panel(app, AlternateScreenContent::FileInfo);
+        }
+        KeyCode::Char('y') => app.copy_logs(false)?,
+        KeyCode::Char('Y') => app.copy_logs(true)?,
+        KeyCode::Char('c') => app.copy_up_all_command_line()?,
+        KeyCode::Char('I') => app.copy_session_report()?,
+        KeyCode::Char('t') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.toggle_split_pane();
+        }
+        KeyCode::Char('u') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.clear_split_panes();
+        }
+        KeyCode::Char('t') => app.toggle_timestamps().await?,
+        KeyCode::Char(c @ ('z' | 'Z')) => match app.export_logs(c == 'Z') {
+            Ok(file_name) => app.set_status(format!("Logs exported to {file_name}")),
+            Err(e) => app.report_error(format!("{e:#}")),
+        },
+        KeyCode::Char('o') if key_event.modifiers == KeyModifiers::CONTROL && is_details(app) => {
+            app.focus_order = app.focus_order.next();
+            app.set_status(format!("Focus order: {:?}", app.focus_order).to_lowercase());
+        }
+        KeyCode::Char('o') => app.toggle_hide_stopped(),
+        KeyCode::Char('b') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.show_scrollbars = !app.show_scrollbars;
+        }
+        KeyCode::Char('b') => app.jump_to_container_event(),
+        KeyCode::Char('L') => app.restart_log_streaming().await?,
+        KeyCode::Char('C') => app.prompt = Some(Prompt::new(PromptKind::Copy)),
+        KeyCode::Char('F') => {
+            let mut prompt = Prompt::new(PromptKind::LabelSelector);
+            prompt.input = app.label_selector.clone().unwrap_or_default();
+            app.prompt = Some(prompt);
+        }
+        KeyCode::Char('l') if key_event.modifiers == KeyModifiers::NONE => {
+            app.prompt = Some(Prompt::new(PromptKind::Since));
+        }
+        KeyCode::End => {
+            app.auto_scroll = !app.auto_scroll;
+            let state = if app.auto_scroll { "on" } else { "off" };
+            app.set_status(format!("Follow the logs: {state}"));
+        }
+        KeyCode::Char('Q') => app.confirm = Some(Confirm::QuitAndDown),
+        KeyCode::Char('A') => app.toggle_all_logs(),
+        KeyCode::Char('H') => app.toggle_log_marker(),
+        KeyCode::Char('U') => app.show_uptime = !app.show_uptime,
+        KeyCode::Char('X') => app.show_limits = !app.show_limits,
+        KeyCode::Char('K') => app.compact_logs = !app.compact_logs,
+        KeyCode::Char(c @ (']' | '[')) => app.resize_tail(c == ']').await?,
+        KeyCode::Char('m') => app.toggle_quiet(),
+        KeyCode::Char('i') => {
+            app.select_first_interesting();
+            app.reset_scroll();
+        }
+
         KeyCode::Enter => {
-            match app.alternate_screen_content {
-                AlternateScreenContent::Help | AlternateScreenContent::ContainerDetails(_) => {
-                    app.alternate_screen_content = AlternateScreenContent::None;
-                    return Ok(());
-                }
-                _ => {}
-            };
+            if app.alternate_screen_content != AlternateScreenContent::None {
+                app.alternate_screen_content = AlternateScreenContent::None;
+                return Ok(());
+            }
             if app.show_popup {
                 app.show_popup = false;
                 app.reset_popup_scroll();
@@ -112,17 +440,10 @@ pub async fn handle_key_events(
 
             if let Some(child) = app.dc(true) {
                 app.queue(QueueType::Start);
-                tokio::spawn(async move {
-                    let op = child.wait_with_output().await.unwrap();
-                    if !op.status.success() {
-                        tx.send(DockerEvent::ErrorLog(
-                            String::from_utf8_lossy(&op.stderr).into(),
-                        ))
-                        .await
-                        .unwrap()
-                    }
-                    tx.send(DockerEvent::Refresh).await.unwrap()
-                });
+                let notification = app.notification("up", false);
+                let audit = app.audit_entry("up", false);
+                let started = app.compose_content.state.selected().into_iter().collect();
+                spawn_compose_up(app, child, tx, notification, audit, started);
             }
         }
         KeyCode::Char('s') => {
@@ -130,92 +451,103 @@ pub async fn handle_key_events(
 
             if let Some(child) = app.dc(false) {
                 app.queue(QueueType::Stop);
-                tokio::spawn(async move {
-                    let op = child.wait_with_output().await.unwrap();
-                    if !op.status.success() {
-                        tx.send(DockerEvent::ErrorLog(
-                            String::from_utf8_lossy(&op.stderr).into(),
-                        ))
-                        .await
-                        .unwrap()
-                    }
-                    tx.send(DockerEvent::Refresh).await.unwrap()
-                });
+                let audit = app.audit_entry("down", false);
+                spawn_compose(app, child, tx, None, audit);
+            }
+        }
+
+        KeyCode::Char('S') => {
+            app.clear_latest_error_log();
+            if let Some(child) = app.stop() {
+                app.queue(QueueType::Stop);
+                let audit = app.audit_entry("stop", false);
+                spawn_compose(app, child, tx, None, audit);
+            }
+        }
+        KeyCode::Char('T') => {
+            app.clear_latest_error_log();
+            if let Some(child) = app.start() {
+                app.queue(QueueType::Start);
+                let audit = app.audit_entry("start", false);
+                let started = app.compose_content.state.selected().into_iter().collect();
+                spawn_compose_up(app, child, tx, None, audit, started);
             }
         }
 
         KeyCode::Char('f') => {
             app.refresh().await?;
+            if app.alternate_screen_content == AlternateScreenContent::ComposePs {
+                app.fetch_compose_ps().await;
+            }
         }
 
+        KeyCode::Char('a') if key_event.modifiers == KeyModifiers::CONTROL => app.toggle_bookmark(),
         KeyCode::Char('a') => {
             app.clear_latest_error_log();
             let child = app.all();
             app.queue_all(QueueType::Start);
-            tokio::spawn(async move {
-                let op = child.wait_with_output().await.unwrap();
-                if !op.status.success() {
-                    tx.send(DockerEvent::ErrorLog(
-                        String::from_utf8_lossy(&op.stderr).into(),
-                    ))
-                    .await
-                    .unwrap()
-                }
-                tx.send(DockerEvent::Refresh).await.unwrap();
-            });
+            let notification = app.notification("up", true);
+            let audit = app.audit_entry("up", true);
+            let started = (0..app.services_len).collect();
+            spawn_compose_up(app, child, tx, notification, audit, started);
         }
         KeyCode::Char('l') if key_event.modifiers == KeyModifiers::CONTROL => {
             app.clear_current_log();
         }
-        KeyCode::Char('x') => {
+        KeyCode::Char('x') if key_event.modifiers == KeyModifiers::CONTROL => {
+            toggle_panel(app, AlternateScreenContent::Dependencies);
+        }
+        KeyCode::Char('x') => destructive(app, DestructiveAction::DownAll),
+        KeyCode::Char('r') if key_event.modifiers == KeyModifiers::CONTROL => {
             app.clear_latest_error_log();
-            let child = app.down_all();
-            app.queue_all(QueueType::Stop);
-            tokio::spawn(async move {
-                let op = child.wait_with_output().await.unwrap();
-                if !op.status.success() {
-                    tx.send(DockerEvent::ErrorLog(
-                        String::from_utf8_lossy(&op.stderr).into(),
-                    ))
-                    .await
-                    .unwrap()
-                }
-                tx.send(DockerEvent::Refresh).await.unwrap();
-            });
+            if let Some((child, audit)) = app.retry_failed() {
+                spawn_compose(app, child, tx, None, audit);
+            }
         }
         KeyCode::Char('r') => {
             app.clear_latest_error_log();
             if let Some(child) = app.restart() {
                 app.queue(QueueType::Start);
-                tokio::spawn(async move {
-                    let op = child.wait_with_output().await.unwrap();
-                    if !op.status.success() {
-                        tx.send(DockerEvent::ErrorLog(
-                            String::from_utf8_lossy(&op.stderr).into(),
-                        ))
-                        .await
-                        .unwrap()
-                    }
-                    tx.send(DockerEvent::Refresh).await.unwrap()
-                });
+                let audit = app.audit_entry("restart", false);
+                let started = app.compose_content.state.selected().into_iter().collect();
+                spawn_compose_up(app, child, tx, None, audit, started);
+            }
+        }
+        KeyCode::Char('R') => {
+            if let Some(selected) = app.compose_content.state.selected() {
+                let service = app.compose_content.compose.services.0.keys()[selected].clone();
+                app.confirm = Some(Confirm::Reset(service));
+            }
+        }
+        KeyCode::Char('u') => {
+            app.clear_latest_error_log();
+            if let Some(child) = app.pull_and_recreate() {
+                app.queue(QueueType::Start);
+                let notification = app.notification("pull and recreate", false);
+                let audit = app.audit_entry("pull and recreate", false);
+                let started = app.compose_content.state.selected().into_iter().collect();
+                spawn_compose_up(app, child, tx, notification, audit, started);
             }
         }
-        KeyCode::Char(c) if ['1', '2', '3', '4', '5'].contains(&c) => {
+        KeyCode::Char(c) if ['1', '2', '3', '4', '5', '6', '7'].contains(&c) => {
             app.toggle_modifier(c);
         }
 
+        KeyCode::Char('k') if key_event.modifiers == KeyModifiers::CONTROL => app.interrupt(),
+        KeyCode::Char('g') if key_event.modifiers == KeyModifiers::CONTROL => app.redraw = true,
+        KeyCode::Char('e') if key_event.modifiers == KeyModifiers::CONTROL => {
+            app.wrap_chars = !app.wrap_chars;
+        }
         KeyCode::Char('j') | KeyCode::PageUp => scroll_up(app, 1),
         KeyCode::Char('k') | KeyCode::PageDown => scroll_down(app, 1),
 
         KeyCode::Char('w') if key_event.modifiers == KeyModifiers::CONTROL => {
-            app.clear_current_log();
-            app.remove_container(true, tx.clone()).await?;
+            destructive(app, DestructiveAction::Remove)
         }
         KeyCode::Char('w')
             if key_event.modifiers == (KeyModifiers::CONTROL | KeyModifiers::ALT) =>
         {
-            app.clear_current_log();
-            app.wipe(true, tx.clone()).await?;
+            destructive(app, DestructiveAction::Wipe)
         }
         KeyCode::Char('h') => {
             if app.alternate_screen_content != AlternateScreenContent::Help {
@@ -235,18 +567,78 @@ pub async fn handle_key_events(
                 app.alternate_screen_content = AlternateScreenContent::None;
             }
         }
+        KeyCode::Char('p') if key_event.modifiers == KeyModifiers::CONTROL => {
+            toggle_panel(app, AlternateScreenContent::ComposePs);
+            if app.alternate_screen_content == AlternateScreenContent::ComposePs {
+                app.fetch_compose_ps().await;
+            }
+        }
+        KeyCode::Char('p') => app.switch_project(1).await?,
+        KeyCode::Char('P') => app.switch_project(-1).await?,
+        KeyCode::Char('d') if key_event.modifiers == KeyModifiers::CONTROL => {
+            toggle_panel(app, AlternateScreenContent::DaemonEvents);
+        }
+        KeyCode::Char('d') => toggle_panel(app, AlternateScreenContent::Diff),
+        KeyCode::Char('v') => toggle_panel(app, AlternateScreenContent::Environment),
+        KeyCode::Char('g') => toggle_panel(app, AlternateScreenContent::Resources),
+        KeyCode::Char('D') => toggle_panel(app, AlternateScreenContent::Dashboard),
+        KeyCode::Char('B') => toggle_panel(app, AlternateScreenContent::Build),
+        KeyCode::Char('W') => toggle_panel(app, AlternateScreenContent::Diagnostics),
+        KeyCode::Char('N') if app.search_query.is_some() => app.jump_to_search_match(false),
+        KeyCode::Char('N') => toggle_panel(app, AlternateScreenContent::Names),
+        KeyCode::Char('J') => toggle_panel(app, AlternateScreenContent::Activity),
+        KeyCode::Char('M') => {
+            if app.alternate_screen_content != AlternateScreenContent::Logs {
+                app.alternate_screen_content = AlternateScreenContent::Logs;
+            } else {
+                app.alternate_screen_content = AlternateScreenContent::None;
+            }
+        }
+        KeyCode::BackTab if is_split_logs(app) => app.cycle_split_focus(false),
         KeyCode::BackTab => {
             if let AlternateScreenContent::ContainerDetails(state) = app.alternate_screen_content {
-                app.alternate_screen_content =
-                    AlternateScreenContent::ContainerDetails(state.transition_back());
+                app.alternate_screen_content = AlternateScreenContent::ContainerDetails(
+                    state.transition_back(app.focus_order),
+                );
+                app.alternate_screen.horizontal_scroll = 0;
             }
         }
+        KeyCode::Tab if app.alternate_screen_content == AlternateScreenContent::Activity => {
+            app.activity_filter = app.activity_filter.next();
+        }
+        KeyCode::Tab if is_split_logs(app) => app.cycle_split_focus(true),
         KeyCode::Tab => {
             if let AlternateScreenContent::ContainerDetails(state) = app.alternate_screen_content {
                 app.alternate_screen_content =
-                    AlternateScreenContent::ContainerDetails(state.transition());
+                    AlternateScreenContent::ContainerDetails(state.transition(app.focus_order));
+                app.alternate_screen.horizontal_scroll = 0;
+            }
+        }
+        KeyCode::Char('w') if is_details(app) => app.alternate_screen.toggle_wrap(),
+        KeyCode::Char('E') if is_details(app) => {
+            if let AlternateScreenContent::ContainerDetails(focused) = app.alternate_screen_content
+            {
+                app.alternate_screen.toggle_sync_scroll(focused);
             }
         }
+        KeyCode::Char('G') if is_details(app) => app.alternate_screen.toggle_label_grouping(),
+        KeyCode::Char('G') => app.next_bookmark(),
+        KeyCode::Char('V') if is_details(app) => app.toggle_mount_listing(),
+        KeyCode::Char('O') if is_details(app) => match app.open_selected_mount() {
+            Ok(path) => app.set_status(format!("Opened {}", path.display())),
+            Err(e) => app.report_error(format!("{e:#}")),
+        },
+        KeyCode::Char(' ') if is_details(app) => app.toggle_label_group(),
+        KeyCode::Char(' ') => app.collapse_selected_group(),
+        KeyCode::Char('+') => app.expand_all_groups(),
+        KeyCode::Char('V') => app.cycle_grouping(),
+        KeyCode::Left if is_details(app) => {
+            app.alternate_screen.horizontal_scroll =
+                app.alternate_screen.horizontal_scroll.saturating_sub(4);
+        }
+        KeyCode::Right if is_details(app) && !app.alternate_screen.wrap => {
+            app.alternate_screen.horizontal_scroll += 4;
+        }
 
         _ => {}
     }
@@ -258,6 +650,14 @@ pub async fn handle_mouse_events(
     app: &mut App,
     _tx: Sender<DockerEvent>,
 ) -> anyhow::Result<()> {
+    if app.show_popup
+        && matches!(
+            mouse_event.kind,
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+        )
+    {
+        app.popup_deadline = None;
+    }
     match mouse_event.kind {
         MouseEventKind::ScrollUp => scroll_up(app, 5),
         MouseEventKind::ScrollDown => scroll_down(app, 5),
@@ -266,6 +666,416 @@ pub async fn handle_mouse_events(
     Ok(())
 }
 
+/// Waits for the compose command in the background, reporting its errors and refreshing once it's done.
+/// If a `notification` is given, a desktop notification is sent with it when the command finishes.
+fn spawn_compose(
+    app: &mut App,
+    child: Child,
+    tx: Sender<DockerEvent>,
+    notification: Option<String>,
+    audit: Option<AuditEntry>,
+) {
+    spawn_compose_reporting(app, child, tx, notification, audit, None, Vec::new(), false);
+}
+
+/// Same as [`spawn_compose`] for commands starting the given services, which are checked for
+/// crashes a while after the command succeeds, unless that's turned off.
+fn spawn_compose_up(
+    app: &mut App,
+    child: Child,
+    tx: Sender<DockerEvent>,
+    notification: Option<String>,
+    audit: Option<AuditEntry>,
+    started: Vec<usize>,
+) {
+    let started = if app.crash_check {
+        started
+            .into_iter()
+            .filter_map(|idx| app.container_name_mapping.get(&idx).cloned())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    spawn_compose_reporting(app, child, tx, notification, audit, None, started, true);
+}
+
+/// Same as [`spawn_compose`], but also shows the `success` status message if the command succeeds.
+/// With `live` set, the output is shown on the popup line by line while the command runs.
+#[allow(clippy::too_many_arguments)]
+fn spawn_compose_reporting(
+    app: &mut App,
+    child: Child,
+    tx: Sender<DockerEvent>,
+    notification: Option<String>,
+    audit: Option<AuditEntry>,
+    success: Option<String>,
+    started: Vec<String>,
+    live: bool,
+) {
+    app.in_flight += 1;
+    let commands = app.running_commands.clone();
+    let pid = commands.track(&child);
+    // The id is only missing if the command already exited, then it has no output to stream anyway.
+    let output_id = pid.unwrap_or_default();
+    let command_line = app.last_spawned.take();
+    let audit =
+        audit.map(|audit| audit.with_command(command_line.as_ref().map(ToString::to_string)));
+    let output_tx = app.output_tx.clone();
+    tokio::spawn(async move {
+        let op = if live {
+            wait_streaming(child, output_id, &output_tx).await.unwrap()
+        } else {
+            child.wait_with_output().await.unwrap()
+        };
+        let interrupted = commands.finish(pid);
+        if let Some(notification) = notification {
+            notify_finished(notification, op.status.success());
+        }
+        if let Some(audit) = audit {
+            audit.finish_command(op.status, interrupted);
+        }
+        if let Some(command_line) = command_line.filter(|_| !interrupted) {
+            let event = if op.status.success() {
+                DockerEvent::CommandSucceeded(command_line)
+            } else {
+                DockerEvent::CommandFailed(command_line)
+            };
+            tx.send(event).await.unwrap();
+        }
+        if live {
+            // The errors were streamed with the rest of the output, they're only reported on their
+            // own if the popup showed another command.
+            let error = (!interrupted && !op.status.success())
+                .then(|| String::from_utf8_lossy(&op.stderr).into_owned());
+            tx.send(DockerEvent::OutputFinished(output_id, error))
+                .await
+                .unwrap();
+        }
+        if interrupted {
+            tx.send(DockerEvent::Progress(String::from("Operation cancelled")))
+                .await
+                .unwrap();
+        } else if !op.status.success() {
+            if !live {
+                tx.send(DockerEvent::ErrorLog(
+                    String::from_utf8_lossy(&op.stderr).into(),
+                ))
+                .await
+                .unwrap()
+            }
+        } else if let Some(success) = success {
+            tx.send(DockerEvent::Progress(success)).await.unwrap();
+        }
+        tx.send(DockerEvent::Refresh).await.unwrap();
+        tx.send(DockerEvent::Finished).await.unwrap();
+        // Compose reports success once the containers are started, they may exit right after.
+        if op.status.success() && !interrupted && !started.is_empty() {
+            tokio::time::sleep(CRASH_CHECK_DELAY).await;
+            tx.send(DockerEvent::CheckStarted(started)).await.unwrap();
+        }
+    });
+}
+
+/// Waits for the command like [`Child::wait_with_output`], sending each line of its output as it arrives.
+async fn wait_streaming(
+    mut child: Child,
+    id: u32,
+    tx: &UnboundedSender<(u32, String)>,
+) -> std::io::Result<std::process::Output> {
+    let stdout = forward_lines(child.stdout.take(), id, tx.clone());
+    let stderr = forward_lines(child.stderr.take(), id, tx.clone());
+    let (stdout, stderr) = tokio::join!(stdout, stderr);
+    let status = child.wait().await?;
+    Ok(std::process::Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+/// Sends the lines of the pipe with the id of the command until it closes, returning everything it read.
+///
+/// The channel is unbounded, so a command with a lot of output never waits for the UI to catch up.
+async fn forward_lines(
+    pipe: Option<impl AsyncRead + Unpin>,
+    id: u32,
+    tx: UnboundedSender<(u32, String)>,
+) -> Vec<u8> {
+    let mut output = Vec::new();
+    let Some(pipe) = pipe else {
+        return output;
+    };
+    let mut lines = BufReader::new(pipe).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        output.extend_from_slice(line.as_bytes());
+        output.push(b'\n');
+        // The receiver is only gone when dcr is exiting.
+        tx.send((id, line)).ok();
+    }
+    output
+}
+
+/// Whether the main screen shows the split logs, where the focus and the scrolling go to the panes.
+fn is_split_logs(app: &App) -> bool {
+    matches!(
+        app.alternate_screen_content,
+        AlternateScreenContent::None | AlternateScreenContent::Logs
+    ) && app.split_active()
+}
+
+fn is_details(app: &App) -> bool {
+    matches!(
+        app.alternate_screen_content,
+        AlternateScreenContent::ContainerDetails(_)
+    )
+}
+
+/// Asks for the confirmation of the destructive action, or for the project name in safe mode.
+fn destructive(app: &mut App, action: DestructiveAction) {
+    let containers: Vec<String> = match action {
+        DestructiveAction::Remove => app
+            .compose_content
+            .state
+            .selected()
+            .and_then(|selected| app.container_name_mapping.get(&selected))
+            .filter(|name| {
+                app.running_container_names.contains(name)
+                    || app.stopped_container_names.contains(name)
+            })
+            .cloned()
+            .into_iter()
+            .collect(),
+        // Only the containers that exist are removed, the services never started have none.
+        DestructiveAction::Wipe => app
+            .container_name_mapping
+            .values()
+            .filter(|name| {
+                app.running_container_names.contains(name)
+                    || app.stopped_container_names.contains(name)
+            })
+            .cloned()
+            .collect(),
+        // Compose tears down the whole project, or only the services given with `--services`.
+        DestructiveAction::DownAll => app.project_container_names(),
+    };
+    if containers.is_empty() {
+        app.set_status(String::from("There are no containers to remove"));
+        return;
+    }
+    if app.safe {
+        let mut prompt = Prompt::new(PromptKind::Destructive(action));
+        prompt.affected = containers;
+        app.prompt = Some(prompt);
+    } else {
+        app.confirm = Some(Confirm::Destructive(action, containers));
+    }
+}
+
+async fn run_destructive(
+    app: &mut App,
+    action: DestructiveAction,
+    tx: Sender<DockerEvent>,
+) -> anyhow::Result<()> {
+    match action {
+        DestructiveAction::Remove => {
+            app.clear_current_log();
+            app.remove_container(true).await
+        }
+        DestructiveAction::Wipe => {
+            app.clear_current_log();
+            app.wipe(true).await
+        }
+        DestructiveAction::DownAll => {
+            app.clear_latest_error_log();
+            let child = app.down_all();
+            app.queue_all(QueueType::Stop);
+            let audit = app.audit_entry("down", true);
+            spawn_compose(app, child, tx, None, audit);
+            Ok(())
+        }
+    }
+}
+
+/// Acts on the submitted text of the prompt, showing what went wrong on the error popup.
+async fn submit_prompt(app: &mut App, prompt: Prompt, tx: Sender<DockerEvent>) {
+    let result = match prompt.kind {
+        PromptKind::Since => app.set_since_window(&prompt.input),
+        PromptKind::LabelSelector => {
+            app.set_label_selector(&prompt.input);
+            Ok(())
+        }
+        PromptKind::Copy => app.copy_files(&prompt.input).map(|(child, success)| {
+            let audit = app.audit_entry("cp", false);
+            spawn_compose_reporting(
+                app,
+                child,
+                tx,
+                None,
+                audit,
+                Some(success),
+                Vec::new(),
+                false,
+            );
+        }),
+        PromptKind::Search => {
+            app.set_search_query(&prompt.input);
+            app.jump_to_search_match(true);
+            Ok(())
+        }
+        PromptKind::ServiceFilter => {
+            app.set_service_filter(&prompt.input);
+            Ok(())
+        }
+        PromptKind::Grep => {
+            app.grep(&prompt.input);
+            app.alternate_screen_content = AlternateScreenContent::None;
+            toggle_panel(app, AlternateScreenContent::Grep);
+            Ok(())
+        }
+        PromptKind::Destructive(action) => {
+            if app.confirms_project(&prompt.input) {
+                run_destructive(app, action, tx).await
+            } else {
+                Err(anyhow::anyhow!(
+                    "The typed name doesn't match the project name {}, nothing was done.",
+                    app.project_name
+                ))
+            }
+        }
+    };
+    if let Err(e) = result {
+        app.report_error(format!("{e:#}"));
+    }
+}
+
+/// Sends the desktop notification about a finished command without blocking the runtime.
+fn notify_finished(notification: String, success: bool) {
+    let body = if success {
+        "Finished successfully"
+    } else {
+        "Failed"
+    };
+    tokio::task::spawn_blocking(move || {
+        // Not every environment has a notification server, there's nothing to do about it.
+        notify_rust::Notification::new()
+            .appname("dcr")
+            .summary(&notification)
+            .body(body)
+            .show()
+            .ok();
+    });
+}
+
+/// Removes the container of the selected service, then starts it again, reporting each step.
+fn spawn_reset(app: &mut App, remove_volumes: bool, tx: Sender<DockerEvent>) {
+    let Some(selected) = app.compose_content.state.selected() else {
+        return;
+    };
+    let Some(mut up) = app.up_command() else {
+        return;
+    };
+    let service = app.compose_content.compose.services.0.keys()[selected].clone();
+    let container_name = app.container_name_mapping[&selected].clone();
+    let docker = app.docker.clone();
+    let commands = app.running_commands.clone();
+    let notification = app.notification("reset", false);
+    let audit = app
+        .audit_entry(
+            if remove_volumes {
+                "reset with volumes"
+            } else {
+                "reset"
+            },
+            false,
+        )
+        .map(|audit| audit.with_command(Some(CommandLine::of(&up).to_string())));
+    app.clear_current_log();
+    app.queue(QueueType::Start);
+    app.in_flight += 1;
+
+    tokio::spawn(async move {
+        tx.send(DockerEvent::Progress(format!(
+            "Resetting {service}: removing the container"
+        )))
+        .await
+        .unwrap();
+        let removed = docker
+            .remove_container(
+                &container_name,
+                Some(RemoveContainerOptions {
+                    v: remove_volumes,
+                    force: true,
+                    ..Default::default()
+                }),
+            )
+            .await;
+        match removed {
+            // There's nothing to remove if the container doesn't exist yet.
+            Ok(_)
+            | Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => {
+                tx.send(DockerEvent::Progress(format!(
+                    "Resetting {service}: starting it again"
+                )))
+                .await
+                .unwrap();
+                let child = up.spawn().unwrap();
+                let pid = commands.track(&child);
+                let op = child.wait_with_output().await.unwrap();
+                let interrupted = commands.finish(pid);
+                if let Some(notification) = notification {
+                    notify_finished(notification, op.status.success());
+                }
+                if let Some(audit) = audit {
+                    audit.finish_command(op.status, interrupted);
+                }
+                if interrupted {
+                    tx.send(DockerEvent::Progress(format!(
+                        "Reset of {service} cancelled"
+                    )))
+                    .await
+                    .unwrap();
+                } else if op.status.success() {
+                    tx.send(DockerEvent::Progress(format!("Reset {service}")))
+                        .await
+                        .unwrap();
+                } else {
+                    tx.send(DockerEvent::ErrorLog(
+                        String::from_utf8_lossy(&op.stderr).into(),
+                    ))
+                    .await
+                    .unwrap();
+                }
+            }
+            Err(e) => {
+                if let Some(audit) = audit {
+                    audit.finish(Outcome::Failure(None));
+                }
+                tx.send(DockerEvent::ErrorLog(format!(
+                    "Failed to remove the container of {service}: {e}"
+                )))
+                .await
+                .unwrap()
+            }
+        }
+        tx.send(DockerEvent::Refresh).await.unwrap();
+        tx.send(DockerEvent::Finished).await.unwrap();
+    });
+}
+
+/// Opens the single panel screen, or closes it if it's already open.
+fn toggle_panel(app: &mut App, panel: AlternateScreenContent) {
+    if app.alternate_screen_content != panel {
+        app.alternate_screen_content = panel;
+        app.alternate_screen.panel_scroll = 0;
+        app.alternate_screen.panel_scroll_state =
+            app.alternate_screen.panel_scroll_state.position(0);
+    } else {
+        app.alternate_screen_content = AlternateScreenContent::None;
+    }
+}
+
 fn scroll_up(app: &mut App, amount: usize) {
     if app.show_popup {
         app.popup_scroll = app.popup_scroll.saturating_sub(amount);
@@ -273,49 +1083,69 @@ fn scroll_up(app: &mut App, amount: usize) {
     } else if let AlternateScreenContent::ContainerDetails(split_screen) =
         app.alternate_screen_content
     {
-        match split_screen {
-            SplitScreen::UpperLeft => {
-                app.alternate_screen.upper_left_scroll = app
-                    .alternate_screen
-                    .upper_left_scroll
-                    .saturating_sub(amount);
-                app.alternate_screen.upper_left_scroll_state = app
-                    .alternate_screen
-                    .upper_left_scroll_state
-                    .position(app.alternate_screen.upper_left_scroll);
-            }
-            SplitScreen::LowerLeft => {
-                app.alternate_screen.lower_left_scroll = app
-                    .alternate_screen
-                    .lower_left_scroll
-                    .saturating_sub(amount);
-                app.alternate_screen.lower_left_scroll_state = app
-                    .alternate_screen
-                    .lower_left_scroll_state
-                    .position(app.alternate_screen.lower_left_scroll);
-            }
-            SplitScreen::UpperRight => {
-                app.alternate_screen.upper_right_scroll = app
-                    .alternate_screen
-                    .upper_right_scroll
-                    .saturating_sub(amount);
-                app.alternate_screen.upper_right_scroll_state = app
-                    .alternate_screen
-                    .upper_right_scroll_state
-                    .position(app.alternate_screen.upper_right_scroll);
-            }
-            SplitScreen::LowerRight => {
-                app.alternate_screen.lower_right_scroll = app
-                    .alternate_screen
-                    .lower_right_scroll
-                    .saturating_sub(amount);
-                app.alternate_screen.lower_right_scroll_state = app
-                    .alternate_screen
-                    .lower_right_scroll_state
-                    .position(app.alternate_screen.lower_right_scroll);
+        let quadrants = if app.alternate_screen.sync_scroll {
+            SplitScreen::ALL.to_vec()
+        } else {
+            vec![split_screen]
+        };
+        for quadrant in quadrants {
+            match quadrant {
+                SplitScreen::UpperLeft => {
+                    app.alternate_screen.upper_left_scroll = app
+                        .alternate_screen
+                        .upper_left_scroll
+                        .saturating_sub(amount);
+                    app.alternate_screen.upper_left_scroll_state = app
+                        .alternate_screen
+                        .upper_left_scroll_state
+                        .position(app.alternate_screen.upper_left_scroll);
+                }
+                SplitScreen::LowerLeft => {
+                    app.alternate_screen.lower_left_scroll = app
+                        .alternate_screen
+                        .lower_left_scroll
+                        .saturating_sub(amount);
+                    app.alternate_screen.lower_left_scroll_state = app
+                        .alternate_screen
+                        .lower_left_scroll_state
+                        .position(app.alternate_screen.lower_left_scroll);
+                }
+                SplitScreen::UpperRight => {
+                    app.alternate_screen.upper_right_scroll = app
+                        .alternate_screen
+                        .upper_right_scroll
+                        .saturating_sub(amount);
+                    app.alternate_screen.upper_right_scroll_state = app
+                        .alternate_screen
+                        .upper_right_scroll_state
+                        .position(app.alternate_screen.upper_right_scroll);
+                }
+                SplitScreen::LowerRight => {
+                    app.alternate_screen.lower_right_scroll = app
+                        .alternate_screen
+                        .lower_right_scroll
+                        .saturating_sub(amount);
+                    app.alternate_screen.lower_right_scroll_state = app
+                        .alternate_screen
+                        .lower_right_scroll_state
+                        .position(app.alternate_screen.lower_right_scroll);
+                }
             }
         }
+    } else if app.alternate_screen_content == AlternateScreenContent::Grep {
+        app.move_grep_selection(-(amount as isize));
+    } else if app.alternate_screen_content.is_panel() {
+        app.alternate_screen.panel_scroll =
+            app.alternate_screen.panel_scroll.saturating_sub(amount);
+        app.alternate_screen.panel_scroll_state = app
+            .alternate_screen
+            .panel_scroll_state
+            .position(app.alternate_screen.panel_scroll);
+    } else if is_split_logs(app) {
+        app.scroll_split_pane(-(amount as isize));
     } else {
+        // Reading the history, so the new lines shouldn't pull the view back down.
+        app.auto_scroll = false;
         app.vertical_scroll = app.vertical_scroll.saturating_sub(amount);
         app.vertical_scroll_state = app.vertical_scroll_state.position(app.vertical_scroll);
     }
@@ -328,48 +1158,66 @@ fn scroll_down(app: &mut App, amount: usize) {
     } else if let AlternateScreenContent::ContainerDetails(split_screen) =
         app.alternate_screen_content
     {
-        match split_screen {
-            SplitScreen::UpperLeft => {
-                app.alternate_screen.upper_left_scroll = app
-                    .alternate_screen
-                    .upper_left_scroll
-                    .saturating_add(amount);
-                app.alternate_screen.upper_left_scroll_state = app
-                    .alternate_screen
-                    .upper_left_scroll_state
-                    .position(app.alternate_screen.upper_left_scroll);
-            }
-            SplitScreen::LowerLeft => {
-                app.alternate_screen.lower_left_scroll = app
-                    .alternate_screen
-                    .lower_left_scroll
-                    .saturating_add(amount);
-                app.alternate_screen.lower_left_scroll_state = app
-                    .alternate_screen
-                    .lower_left_scroll_state
-                    .position(app.alternate_screen.lower_left_scroll);
-            }
-            SplitScreen::UpperRight => {
-                app.alternate_screen.upper_right_scroll = app
-                    .alternate_screen
-                    .upper_right_scroll
-                    .saturating_add(amount);
-                app.alternate_screen.upper_right_scroll_state = app
-                    .alternate_screen
-                    .upper_right_scroll_state
-                    .position(app.alternate_screen.upper_right_scroll);
-            }
-            SplitScreen::LowerRight => {
-                app.alternate_screen.lower_right_scroll = app
-                    .alternate_screen
-                    .lower_right_scroll
-                    .saturating_add(amount);
-                app.alternate_screen.lower_right_scroll_state = app
-                    .alternate_screen
-                    .lower_right_scroll_state
-                    .position(app.alternate_screen.lower_right_scroll);
+        let quadrants = if app.alternate_screen.sync_scroll {
+            SplitScreen::ALL.to_vec()
+        } else {
+            vec![split_screen]
+        };
+        for quadrant in quadrants {
+            match quadrant {
+                SplitScreen::UpperLeft => {
+                    app.alternate_screen.upper_left_scroll = app
+                        .alternate_screen
+                        .upper_left_scroll
+                        .saturating_add(amount);
+                    app.alternate_screen.upper_left_scroll_state = app
+                        .alternate_screen
+                        .upper_left_scroll_state
+                        .position(app.alternate_screen.upper_left_scroll);
+                }
+                SplitScreen::LowerLeft => {
+                    app.alternate_screen.lower_left_scroll = app
+                        .alternate_screen
+                        .lower_left_scroll
+                        .saturating_add(amount);
+                    app.alternate_screen.lower_left_scroll_state = app
+                        .alternate_screen
+                        .lower_left_scroll_state
+                        .position(app.alternate_screen.lower_left_scroll);
+                }
+                SplitScreen::UpperRight => {
+                    app.alternate_screen.upper_right_scroll = app
+                        .alternate_screen
+                        .upper_right_scroll
+                        .saturating_add(amount);
+                    app.alternate_screen.upper_right_scroll_state = app
+                        .alternate_screen
+                        .upper_right_scroll_state
+                        .position(app.alternate_screen.upper_right_scroll);
+                }
+                SplitScreen::LowerRight => {
+                    app.alternate_screen.lower_right_scroll = app
+                        .alternate_screen
+                        .lower_right_scroll
+                        .saturating_add(amount);
+                    app.alternate_screen.lower_right_scroll_state = app
+                        .alternate_screen
+                        .lower_right_scroll_state
+                        .position(app.alternate_screen.lower_right_scroll);
+                }
             }
         }
+    } else if app.alternate_screen_content == AlternateScreenContent::Grep {
+        app.move_grep_selection(amount as isize);
+    } else if app.alternate_screen_content.is_panel() {
+        app.alternate_screen.panel_scroll =
+            app.alternate_screen.panel_scroll.saturating_add(amount);
+        app.alternate_screen.panel_scroll_state = app
+            .alternate_screen
+            .panel_scroll_state
+            .position(app.alternate_screen.panel_scroll);
+    } else if is_split_logs(app) {
+        app.scroll_split_pane(amount as isize);
     } else {
         app.vertical_scroll = app.vertical_scroll.saturating_add(amount);
         app.vertical_scroll_state = app.vertical_scroll_state.position(app.vertical_scroll);
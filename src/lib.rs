@@ -1,8 +1,20 @@
 use std::sync::OnceLock;
 
 pub mod app;
+pub mod audit;
+pub mod bookmarks;
+pub mod context;
+pub mod diff;
+pub mod env;
 pub mod event;
+pub mod events;
 pub mod handler;
+pub mod probe;
+pub mod project;
+pub mod recent;
+pub mod remote;
+pub mod stats;
+pub mod status;
 pub mod text_wrap;
 pub mod tui;
 pub mod ui;
@@ -10,5 +22,10 @@ pub mod utils;
 
 /// Maximum number of characters in a path before starting to truncate it.
 pub static MAX_PATH_CHARS: OnceLock<usize> = OnceLock::new();
+/// The pattern of the generated container names.
+pub static NAME_TEMPLATE: OnceLock<project::NameTemplate> = OnceLock::new();
+/// The minimum size of the main screen, below it the resize screen is shown instead.
+pub static MIN_COLS: OnceLock<u16> = OnceLock::new();
+pub static MIN_ROWS: OnceLock<u16> = OnceLock::new();
 /// Whether the light mode is enabled.
 pub static LIGHT_MODE: OnceLock<bool> = OnceLock::new();
@@ -1,36 +1,197 @@
 use anyhow::Context;
-use bollard::container::ListContainersOptions;
-use bollard::Docker;
 use clap::Parser;
-use dcr::app::App;
+use dcr::app::{
+    App, FocusOrder, HighlightStyle, LogFormat, Progress, ServiceGrouping, StartScreen,
+};
 use dcr::event::{Event, EventHandler};
 use dcr::handler::{handle_key_events, handle_mouse_events, DockerEvent};
+use dcr::project::{NameTemplate, ProjectFile, ServiceFilter};
+use dcr::status::StatusFormat;
 use dcr::tui::Tui;
-use dcr::{LIGHT_MODE, MAX_PATH_CHARS};
-use docker_compose_types::Compose;
-use indexmap::IndexMap;
-use miette::LabeledSpan;
+use dcr::{
+    ui::{DEFAULT_MIN_COLS, DEFAULT_MIN_ROWS},
+    LIGHT_MODE, MAX_PATH_CHARS, MIN_COLS, MIN_ROWS, NAME_TEMPLATE,
+};
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use std::collections::HashMap;
 use std::io;
 use std::path::Path;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    #[arg(default_value_t = String::from("docker-compose.yml"))]
-    compose_file: String,
+    /// The compose file to open, `docker-compose.yml` in the current directory if not set.
+    ///
+    /// An `http(s)://` URL is downloaded into a temporary file, and the `.env` and the project
+    /// name come from the current directory. Only open remote files you trust, the compose
+    /// commands run them with your permissions.
+    compose_file: Option<String>,
+
+    /// Reopen the last used compose file when no file is given.
+    #[arg(env = "DCR_REOPEN_LAST", long)]
+    last: bool,
+
+    /// Choose from the recently opened compose files.
+    #[arg(long, conflicts_with = "compose_file")]
+    recent: bool,
+
+    /// Print the status of the services and exit without starting the interface.
+    #[arg(long)]
+    status: bool,
+
+    /// The format of the status printed with `--status`.
+    #[arg(long, value_enum, default_value_t, requires = "status")]
+    format: StatusFormat,
 
     /// Set the maximum path length to display without truncating.
     #[arg(env, long, default_value_t = 40)]
     max_path_len: usize,
 
+    /// The pattern of the container names compose generates, with the `{project}`, `{service}`
+    /// and `{index}` placeholders. The services with a `container_name` keep it. The names panel
+    /// shows where the generated names differ from the ones found by the labels.
+    #[arg(
+        env = "DCR_NAME_TEMPLATE",
+        long,
+        default_value = "{project}-{service}-{index}"
+    )]
+    name_template: NameTemplate,
+
     /// Enable light mode.
     #[arg(env = "DCR_LIGHT_MODE", long)]
     light: bool,
+
+    /// The minimum width of the main screen, a narrower terminal shows the resize screen instead.
+    /// Lowering it lets the layout squeeze, at the cost of truncated text.
+    #[arg(env = "DCR_MIN_COLS", long, default_value_t = DEFAULT_MIN_COLS)]
+    min_cols: u16,
+
+    /// The minimum height of the main screen, a shorter terminal shows the resize screen instead.
+    #[arg(env = "DCR_MIN_ROWS", long, default_value_t = DEFAULT_MIN_ROWS)]
+    min_rows: u16,
+
+    /// Select the first unhealthy or running service on startup instead of the first one.
+    #[arg(env = "DCR_SELECT_RUNNING", long)]
+    select_running: bool,
+
+    /// The maximum number of services to stream logs for at the same time.
+    /// The least recently viewed services are paused beyond this limit. Unlimited if not set.
+    #[arg(env = "DCR_MAX_STREAMS", long)]
+    max_streams: Option<usize>,
+
+    /// Additional compose files to manage, can be repeated. Switch between projects with `p`.
+    #[arg(long = "project")]
+    projects: Vec<String>,
+
+    /// The progress output type of the spawned compose commands.
+    #[arg(env = "DCR_PROGRESS", long, value_enum, default_value_t = Progress::Plain)]
+    progress: Progress,
+
+    /// The format of the exported logs.
+    #[arg(env = "DCR_LOG_FORMAT", long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    /// The symbol in front of the selected service, like `→ `. Can be empty when the
+    /// highlight style is enough.
+    #[arg(env = "DCR_HIGHLIGHT_SYMBOL", long, default_value = ">>")]
+    highlight_symbol: String,
+
+    /// How the selected service stands out, `reverse` highlights the whole row.
+    #[arg(env = "DCR_HIGHLIGHT_STYLE", long, value_enum, default_value_t = HighlightStyle::Emphasis)]
+    highlight_style: HighlightStyle,
+
+    /// The order (tab) moves the focus between the quadrants of the details screen, column by
+    /// column or row by row. It can be switched with ctrl + o in the details.
+    #[arg(env = "DCR_FOCUS_ORDER", long, value_enum, default_value_t = FocusOrder::Columns)]
+    focus_order: FocusOrder,
+
+    /// Close the error popup automatically after this many seconds. Interacting with the popup keeps it open.
+    /// The popup only closes manually if not set or 0.
+    #[arg(env = "DCR_POPUP_TIMEOUT", long)]
+    popup_timeout: Option<u64>,
+
+    /// Send a desktop notification when a start, pull or reset finishes.
+    #[arg(env = "DCR_NOTIFY", long)]
+    notify: bool,
+
+    /// Require typing the project name before destructive actions: removing the selected
+    /// container (ctrl + w), removing all containers (ctrl + alt + w) and stopping all of them (x).
+    #[arg(env = "DCR_SAFE", long)]
+    safe: bool,
+
+    /// Append a JSON line to the file for every action that changes containers, with the
+    /// services, the active modifiers, the command it ran, the time and the outcome.
+    #[arg(env = "DCR_AUDIT_FILE", long, visible_alias = "audit-log")]
+    audit_file: Option<std::path::PathBuf>,
+
+    /// Don't check whether the started services crashed a few seconds after compose reported
+    /// starting them successfully.
+    #[arg(env = "DCR_NO_CRASH_CHECK", long)]
+    no_crash_check: bool,
+
+    /// Offer to run `docker compose down` when quitting with `q` or `Esc`, `Q` always offers it.
+    /// Quitting with `ctrl + c` never tears the project down.
+    #[arg(env = "DCR_DOWN_ON_EXIT", long)]
+    down_on_exit: bool,
+
+    /// Periodically connect to the published TCP ports of the running services, and mark the
+    /// services whose ports don't accept connections. Off by default, as it generates network traffic.
+    #[arg(env = "DCR_PROBE_PORTS", long)]
+    probe_ports: bool,
+
+    /// Redraw the screen only every few seconds while no input arrives and nothing changes,
+    /// which saves power when the interface is left open for monitoring.
+    #[arg(env = "DCR_ECO", long)]
+    eco: bool,
+
+    /// Start with the scrollbars hidden, they can be shown again with ctrl + b.
+    #[arg(env = "DCR_HIDE_SCROLLBARS", long)]
+    hide_scrollbars: bool,
+
+    /// Moving past the last service selects the first one and the other way around.
+    #[arg(env = "DCR_WRAP", long = "wrap")]
+    wrap_around: bool,
+
+    /// How the service list is grouped on startup, cycle through the groupings with `V`.
+    #[arg(env = "DCR_GROUP_BY", long, value_enum, default_value_t = ServiceGrouping::Flat)]
+    group_by: ServiceGrouping,
+
+    /// The label to group the services by with `--group-by label`.
+    #[arg(env = "DCR_GROUP_LABEL", long)]
+    group_label: Option<String>,
+
+    /// Limit the whole interface to the named services of the compose file, comma separated.
+    /// The rest of the services are not listed, streamed, started with (a), or torn down with
+    /// `down`, which then only removes their containers.
+    /// Only applies to the main compose file, not to the ones given with `--projects`.
+    #[arg(env = "DCR_SERVICES", long, value_delimiter = ',')]
+    services: Vec<String>,
+
+    /// Only show and stream the services matching the pattern, can be repeated or comma separated.
+    /// Patterns with `*` or `?` are globs, others match as a substring, and `!pattern` excludes
+    /// the matching services. The filter is applied to all services of the file regardless of
+    /// profiles, so services of inactive profiles still show up if they match. Project wide
+    /// commands like starting all services (a) or `down` still act on the whole project.
+    #[arg(env = "DCR_FILTER", long, value_delimiter = ',')]
+    filter: Vec<String>,
+
+    /// The screen to open on startup.
+    #[arg(env = "DCR_START_SCREEN", long, value_enum, default_value_t = StartScreen::Main)]
+    start_screen: StartScreen,
+
+    /// The Docker context to connect to, also passed to the compose commands.
+    #[arg(env = "DOCKER_CONTEXT", long)]
+    context: Option<String>,
+
+    /// The seconds the containers get to stop on down, stop and restart before they're killed.
+    /// Docker's default of 10 seconds applies if not set.
+    #[arg(env = "DCR_STOP_TIMEOUT", long)]
+    stop_timeout: Option<u32>,
 }
 
+/// The compose file used when nothing else is given.
+const DEFAULT_COMPOSE_FILE: &str = "docker-compose.yml";
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     miette::set_hook(Box::new(|_| {
@@ -41,85 +202,88 @@ async fn main() -> anyhow::Result<()> {
                 .build(),
         )
     }))?;
+    let args = Args::parse();
     #[cfg(unix)]
-    let docker =
-        Docker::connect_with_socket_defaults().context("Failed to connect to Docker daemon")?;
-
-    let mut list_container_filters = HashMap::new();
-    list_container_filters.insert("status", vec!["running"]);
-
-    let containers = &docker
-        .list_containers(Some(ListContainersOptions {
-            all: true,
-            filters: list_container_filters,
-            ..Default::default()
-        }))
-        .await?;
-
-    let running_container_names = containers
-        .iter()
-        .cloned()
-        .filter_map(|c| c.names)
-        .flatten()
-        .map(|name| name.trim_start_matches('/').into())
-        .collect::<Vec<String>>();
+    let docker = dcr::context::connect(args.context.as_deref())
+        .context("Failed to connect to Docker daemon")?;
 
     let Args {
-        compose_file: file,
+        compose_file,
+        last,
+        recent,
+        status,
+        format,
         max_path_len,
+        name_template,
         light,
-    } = Args::parse();
+        select_running,
+        max_streams,
+        projects,
+        progress,
+        log_format,
+        highlight_symbol,
+        highlight_style,
+        focus_order,
+        popup_timeout,
+        notify,
+        safe,
+        audit_file,
+        no_crash_check,
+        down_on_exit,
+        probe_ports,
+        eco,
+        hide_scrollbars,
+        wrap_around,
+        group_by,
+        group_label,
+        services,
+        filter,
+        start_screen,
+        context,
+        stop_timeout,
+        min_cols,
+        min_rows,
+    } = args;
     MAX_PATH_CHARS.set(max_path_len).unwrap();
+    NAME_TEMPLATE.set(name_template).unwrap();
     LIGHT_MODE.set(light).unwrap();
-    let full_path = Path::new(&file).canonicalize()?;
-
-    let file_payload =
-        std::fs::read_to_string(&file).with_context(|| format!("file '{file}' not found"))?;
-    let deserializer = serde_yaml::Deserializer::from_str(&file_payload);
-    let compose_content = match serde_path_to_error::deserialize::<'_, _, Compose>(deserializer) {
-        Ok(c) => c,
-        Err(e) => {
-            let inner = e.into_inner();
-            let Some(location) = inner.location() else {
-                anyhow::bail!("Failed to deserialize compose file.")
+    MIN_COLS.set(min_cols).unwrap();
+    MIN_ROWS.set(min_rows).unwrap();
+    let file = match compose_file {
+        Some(file) => file,
+        None => {
+            let previous = if recent {
+                dcr::recent::pick()?
+            } else if last {
+                dcr::recent::last()
+            } else {
+                None
             };
-            let report = miette::miette!(
-                labels = vec![LabeledSpan::at(location.index(), inner.to_string())],
-                "Failed to deserialize compose file at {}",
-                full_path.display()
-            )
-            .with_source_code(file_payload);
-            anyhow::bail!("{report:?}");
+            previous
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or_else(|| DEFAULT_COMPOSE_FILE.to_string())
         }
     };
-
-    // Try to load the .env from the same directory as the docker-compose file.
-    let dotenv_file = full_path.parent().expect("a directory").join(".env");
-    dotenvy::from_path(dotenv_file).ok();
-
-    let project_name = std::env::var("COMPOSE_PROJECT_NAME").unwrap_or_else(|_| {
-        let components = full_path.components().collect::<Vec<_>>();
-        components
-            .get(components.len().saturating_sub(2))
-            .expect("Failed to determine project name.")
-            .as_os_str()
-            .to_string_lossy()
-            .into_owned()
-    });
-
-    let mut container_name_mapping = IndexMap::new();
-    for (i, (service_name, info)) in compose_content.services.clone().0.iter().enumerate() {
-        let service_name = if let Some(info) = info {
-            if let Some(container_name) = &info.container_name {
-                container_name.clone()
-            } else {
-                // We don't scale services, the 1 index should be fine.
-                format!("{project_name}-{service_name}-1")
-            }
-        } else {
-            format!("{project_name}-{service_name}-1")
-        };
-        container_name_mapping.insert(i, service_name.clone());
+    let remote_file = if dcr::remote::is_url(&file) {
+        dcr::remote::use_working_directory()?;
+        Some(dcr::remote::fetch(&file)?)
+    } else {
+        None
+    };
+    let file = match &remote_file {
+        Some(remote) => remote.path().to_string_lossy().into_owned(),
+        None => file,
+    };
+    let filter = ServiceFilter::new(&filter);
+    let mut project = ProjectFile::load(&file)?;
+    project.retain_named(&services)?;
+    project.retain_services(&filter)?;
+    let schema_warning = project.legacy_schema_warning();
+    // The history is a convenience, failing to save it shouldn't prevent the startup.
+    // The downloaded files are gone after the session, so they're not worth remembering, and
+    // neither are the files only queried for their status.
+    if remote_file.is_none() && !status {
+        dcr::recent::record(Path::new(&file)).ok();
     }
 
     let docker_version = docker
@@ -128,19 +292,57 @@ async fn main() -> anyhow::Result<()> {
         .version
         .unwrap_or_else(|| "unknown".to_string());
 
-    let mut app = App::new(
-        project_name,
-        compose_content,
-        container_name_mapping,
-        running_container_names,
-        docker.clone(),
-        file,
-        full_path,
-        docker_version,
-    );
+    let mut app = App::new(project, docker.clone(), docker_version);
+    app.refresh_container_names().await?;
+    if status {
+        app.fetch_all_container_info().await?;
+        print!("{}", dcr::status::render(&app, format)?);
+        return Ok(());
+    }
+    app.compose_content.only_services = services;
 
+    app.progress = progress;
+    app.log_format = log_format;
+    app.highlight_symbol = highlight_symbol;
+    app.highlight_style = highlight_style;
+    app.focus_order = focus_order;
+    app.notify = notify;
+    app.safe = safe;
+    app.crash_check = !no_crash_check;
+    app.audit = audit_file
+        .as_deref()
+        .map(dcr::audit::AuditLog::open)
+        .transpose()?;
+    app.down_on_exit = down_on_exit;
+    app.probe_ports = probe_ports;
+    app.eco = eco;
+    app.show_scrollbars = !hide_scrollbars;
+    app.wrap_around = wrap_around;
+    app.grouping = group_by;
+    app.group_label = group_label;
+    app.popup_timeout = popup_timeout
+        .filter(|secs| *secs > 0)
+        .map(std::time::Duration::from_secs);
+    app.docker_context = context;
+    app.stop_timeout = stop_timeout;
+    app.set_max_streams(max_streams);
+    app.bookmarks = dcr::bookmarks::load();
+    for file in &projects {
+        let mut project = ProjectFile::load(file)?;
+        project.retain_services(&filter)?;
+        app.add_project(project);
+    }
     app.start_all_log_streaming().await?;
+    app.start_event_stream();
     app.fetch_all_container_info().await?;
+    if select_running {
+        app.select_first_interesting();
+    }
+    app.alternate_screen_content = start_screen.content();
+    // Only checked on startup, so it's shown once per session.
+    if let Some(warning) = schema_warning {
+        app.report_error(warning);
+    }
 
     // Initialize the terminal user interface.
     let backend = CrosstermBackend::new(io::stderr());
@@ -149,32 +351,82 @@ async fn main() -> anyhow::Result<()> {
     let mut tui = Tui::new(terminal, events);
     tui.init()?;
 
-    // We may send 2 messages in one frame, so we need that to be buffered to avoid waiting indefinitely on the sender side.
-    let (tx, mut rx) = tokio::sync::mpsc::channel(2);
+    // We may send 3 messages in one frame, so we need that to be buffered to avoid waiting indefinitely on the sender side.
+    // The output of the running commands goes through its own unbounded channel, see `App::drain_output`.
+    let (tx, mut rx) = tokio::sync::mpsc::channel(3);
 
     while app.running {
-        tui.draw(&mut app)?;
+        if app.should_draw() {
+            tui.draw(&mut app)?;
+        }
 
         match tui.events.next().await? {
-            Event::Tick => app.tick(),
-            Event::Key(key_event) => handle_key_events(key_event, &mut app, tx.clone()).await?,
+            Event::Tick => {
+                // Streams established before the host was suspended are silently dead after waking up.
+                if app.resumed() {
+                    app.reconnect().await?;
+                }
+                app.tick();
+            }
+            Event::Key(key_event) => {
+                app.mark_active();
+                handle_key_events(key_event, &mut app, tx.clone()).await?;
+            }
             Event::Mouse(mouse_event) => {
+                app.mark_active();
                 handle_mouse_events(mouse_event, &mut app, tx.clone()).await?;
             }
-            Event::Resize(_, _) => {}
+            Event::Resize(_, _) => app.mark_active(),
         }
-        if let Ok(docker_event) = rx.try_recv() {
+        app.drain_output();
+        while let Ok(docker_event) = rx.try_recv() {
+            app.mark_active();
             match docker_event {
                 DockerEvent::Refresh => app.refresh().await?,
                 DockerEvent::ErrorLog(log) => {
-                    app.set_error_log(log);
-                    app.show_popup = true;
+                    app.report_error(log);
                     app.clear_starting();
                 }
+                DockerEvent::Finished => app.in_flight = app.in_flight.saturating_sub(1),
+                DockerEvent::Progress(msg) => app.set_status(msg),
+                DockerEvent::OutputFinished(id, error) => {
+                    if error.is_some() {
+                        app.clear_starting();
+                    }
+                    app.finish_output(id, error);
+                }
+                DockerEvent::CommandFailed(command) => app.failed_command = Some(command),
+                DockerEvent::CommandSucceeded(command) => {
+                    if app.failed_command.as_ref() == Some(&command) {
+                        app.failed_command = None;
+                    }
+                }
+                DockerEvent::CheckStarted(started) => {
+                    app.refresh().await?;
+                    app.report_crashed(&started);
+                }
             }
         }
     }
 
     tui.exit()?;
+
+    if app.quit_with_down {
+        eprintln!("Running docker compose down..");
+        let child = app.down_all();
+        let audit = app
+            .audit_entry("down", true)
+            .map(|audit| audit.with_command(app.last_spawned.take().map(|c| c.to_string())));
+        let output = child.wait_with_output().await?;
+        if let Some(audit) = audit {
+            audit.finish_command(output.status, false);
+        }
+        if !output.status.success() {
+            anyhow::bail!(
+                "docker compose down failed:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+    }
     Ok(())
 }
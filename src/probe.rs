@@ -0,0 +1,95 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bollard::secret::ContainerInspectResponse;
+use indexmap::IndexMap;
+use tokio::{net::TcpStream, task::JoinHandle};
+
+/// How long a single connection attempt may take before the port counts as unreachable.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Whether the published ports of a service accept connections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reachability {
+    /// Every published port accepted a connection.
+    Reachable,
+    /// The published ports that refused the connection or timed out, as `host:port`.
+    Unreachable(Vec<String>),
+}
+
+/// The latest probe results of each service with published ports.
+#[derive(Debug, Clone, Default)]
+pub struct PortProbes(Arc<Mutex<IndexMap<usize, Reachability>>>);
+
+impl PortProbes {
+    pub fn get(&self, idx: usize) -> Option<Reachability> {
+        self.0.lock().unwrap().get(&idx).cloned()
+    }
+
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+/// The TCP ports the container publishes on the host, as the address to connect to.
+///
+/// Ports bound to every interface are probed on the loopback address.
+pub fn published_ports(info: &ContainerInspectResponse) -> Vec<String> {
+    let Some(ports) = info
+        .network_settings
+        .as_ref()
+        .and_then(|settings| settings.ports.as_ref())
+    else {
+        return Vec::new();
+    };
+    let mut addresses = ports
+        .iter()
+        .filter(|(port, _)| port.ends_with("/tcp"))
+        .flat_map(|(_, bindings)| bindings.iter().flatten())
+        .filter_map(|binding| {
+            let port = binding
+                .host_port
+                .as_deref()
+                .filter(|port| !port.is_empty())?;
+            let host = match binding.host_ip.as_deref() {
+                Some("" | "0.0.0.0") | None => "127.0.0.1",
+                // The IPv6 binding of the same port is reachable on the IPv4 loopback too.
+                Some("::") => return None,
+                Some(ip) => ip,
+            };
+            Some(format!("{host}:{port}"))
+        })
+        .collect::<Vec<_>>();
+    addresses.sort();
+    addresses.dedup();
+    addresses
+}
+
+/// Connects to the published ports of every service in the background, replacing the previous results.
+pub fn spawn_probes(targets: Vec<(usize, Vec<String>)>, probes: PortProbes) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let results =
+            futures::future::join_all(targets.into_iter().map(|(idx, addresses)| async move {
+                let mut unreachable = Vec::new();
+                for address in addresses {
+                    let connected =
+                        tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(&address))
+                            .await
+                            .is_ok_and(|result| result.is_ok());
+                    if !connected {
+                        unreachable.push(address);
+                    }
+                }
+                let reachability = if unreachable.is_empty() {
+                    Reachability::Reachable
+                } else {
+                    Reachability::Unreachable(unreachable)
+                };
+                (idx, reachability)
+            }))
+            .await;
+        *probes.0.lock().unwrap() = results.into_iter().collect();
+    })
+}
@@ -0,0 +1,394 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use docker_compose_types::Compose;
+use indexmap::IndexMap;
+use miette::LabeledSpan;
+
+use crate::NAME_TEMPLATE;
+
+/// A compose project loaded from a compose file.
+#[derive(Debug)]
+pub struct ProjectFile {
+    /// The name of the compose project either set via .env or the toplevel directory, normalized like compose does.
+    pub project_name: String,
+    /// The full compose file structure deserialized.
+    pub compose: Compose,
+    /// The mapping of docker compose toplevel services to their real container names.
+    pub container_name_mapping: IndexMap<usize, String>,
+    /// The compose file name as it was passed in.
+    pub target: String,
+    /// The full path to the compose file.
+    pub full_path: PathBuf,
+    /// The variables of the `.env` file next to the compose file.
+    pub dotenv: IndexMap<String, String>,
+}
+
+impl ProjectFile {
+    /// Parses the compose file and determines the project name and the container names.
+    ///
+    /// The project name is read from the `COMPOSE_PROJECT_NAME` environment variable, then from the
+    /// `.env` file next to the compose file, and falls back to the parent directory name.
+    ///
+    /// The `.env` file is only kept in [`ProjectFile::dotenv`], it's not copied into the process
+    /// environment, so it can't leak into the other projects.
+    pub fn load(file: &str) -> anyhow::Result<Self> {
+        let full_path = Path::new(file)
+            .canonicalize()
+            .with_context(|| format!("file '{file}' not found"))?;
+
+        let file_payload =
+            std::fs::read_to_string(file).with_context(|| format!("file '{file}' not found"))?;
+        let deserializer = serde_yaml::Deserializer::from_str(&file_payload);
+        let compose = match serde_path_to_error::deserialize::<'_, _, Compose>(deserializer) {
+            Ok(c) => c,
+            Err(e) => {
+                let inner = e.into_inner();
+                let Some(location) = inner.location() else {
+                    anyhow::bail!("Failed to deserialize compose file.")
+                };
+                let report = miette::miette!(
+                    labels = vec![LabeledSpan::at(location.index(), inner.to_string())],
+                    "Failed to deserialize compose file at {}",
+                    full_path.display()
+                )
+                .with_source_code(file_payload);
+                anyhow::bail!("{report:?}");
+            }
+        };
+
+        let dotenv_file = full_path.parent().expect("a directory").join(".env");
+        let dotenv = dotenvy::from_path_iter(dotenv_file)
+            .map(|iter| iter.filter_map(Result::ok).collect::<IndexMap<_, _>>())
+            .unwrap_or_default();
+
+        let project_name = std::env::var("COMPOSE_PROJECT_NAME")
+            .ok()
+            .or_else(|| dotenv.get("COMPOSE_PROJECT_NAME").cloned())
+            .unwrap_or_else(|| {
+                let components = full_path.components().collect::<Vec<_>>();
+                components
+                    .get(components.len().saturating_sub(2))
+                    .expect("Failed to determine project name.")
+                    .as_os_str()
+                    .to_string_lossy()
+                    .into_owned()
+            });
+        // Compose labels and names the containers with the normalized name, `MyStack` becomes `mystack`.
+        let project_name = match normalize_project_name(&project_name) {
+            name if name.is_empty() => anyhow::bail!(
+                "the project name `{project_name}` has no letters or digits, \
+                 set COMPOSE_PROJECT_NAME to name the project"
+            ),
+            name => name,
+        };
+
+        let container_name_mapping = container_names(&project_name, &compose);
+
+        Ok(Self {
+            project_name,
+            compose,
+            container_name_mapping,
+            target: file.to_string(),
+            full_path,
+            dotenv,
+        })
+    }
+
+    /// Drops the services not passing the filter, so they're neither listed nor streamed.
+    /// Fails if none of the services pass it, there would be nothing to manage.
+    pub fn retain_services(&mut self, filter: &ServiceFilter) -> anyhow::Result<()> {
+        if filter.is_empty() {
+            return Ok(());
+        }
+        self.compose
+            .services
+            .0
+            .retain(|name, _| filter.matches(name));
+        if self.compose.services.0.is_empty() {
+            anyhow::bail!("--filter doesn't match any service of {}", self.target);
+        }
+        self.container_name_mapping = container_names(&self.project_name, &self.compose);
+        Ok(())
+    }
+
+    /// Keeps only the named services, failing if any of them is not in the file.
+    pub fn retain_named(&mut self, services: &[String]) -> anyhow::Result<()> {
+        if services.is_empty() {
+            return Ok(());
+        }
+        let unknown = services
+            .iter()
+            .filter(|name| !self.compose.services.0.contains_key(*name))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            anyhow::bail!(
+                "{} doesn't define the service(s): {}",
+                self.target,
+                unknown.join(", ")
+            );
+        }
+        self.compose
+            .services
+            .0
+            .retain(|name, _| services.contains(name));
+        self.container_name_mapping = container_names(&self.project_name, &self.compose);
+        Ok(())
+    }
+
+    /// A warning about the legacy schema if the file declares a version 1 or 2 format.
+    ///
+    /// Those formats handle some keys differently than the Compose Specification, which is what
+    /// the services are parsed with, so some values may not show up as compose resolves them.
+    pub fn legacy_schema_warning(&self) -> Option<String> {
+        let version = self.compose.version.as_deref()?.trim();
+        let major = version.split('.').next()?;
+        if !matches!(major, "1" | "2") {
+            return None;
+        }
+        Some(format!(
+            "Warning: {} declares the legacy compose file format version {version}. \
+            Some keys are interpreted differently by the Compose Specification, so parts of the \
+            file may not be shown correctly. Run `docker compose -f {} config` to see the \
+            resolved configuration, and use (d) to compare it with the running container.",
+            self.target, self.target,
+        ))
+    }
+}
+
+/// The project name the way compose normalizes it: lowercase, keeping only the letters, the digits,
+/// `-` and `_`, and not starting with `-` or `_`.
+pub fn normalize_project_name(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_'))
+        .collect::<String>()
+        .trim_start_matches(['-', '_'])
+        .to_string()
+}
+
+/// The mapping of the service indices to the container names.
+fn container_names(project_name: &str, compose: &Compose) -> IndexMap<usize, String> {
+    let template = NAME_TEMPLATE.get_or_init(NameTemplate::default);
+    let mut container_name_mapping = IndexMap::new();
+    for (i, (service_name, info)) in compose.services.0.iter().enumerate() {
+        let service_name = if let Some(info) = info {
+            if let Some(container_name) = &info.container_name {
+                container_name.clone()
+            } else {
+                template.render(project_name, service_name)
+            }
+        } else {
+            template.render(project_name, service_name)
+        };
+        container_name_mapping.insert(i, service_name);
+    }
+    container_name_mapping
+}
+
+/// The pattern of the container names compose generates for the services without a `container_name`.
+///
+/// The placeholders are `{project}`, `{service}` and `{index}`, like `{project}_{service}_{index}`
+/// for the older compose versions.
+#[derive(Debug, Clone)]
+pub struct NameTemplate(String);
+
+impl NameTemplate {
+    const PLACEHOLDERS: [&'static str; 3] = ["project", "service", "index"];
+
+    pub fn render(&self, project_name: &str, service_name: &str) -> String {
+        // We don't scale services, the 1 index should be fine.
+        self.0
+            .replace("{project}", project_name)
+            .replace("{service}", service_name)
+            .replace("{index}", "1")
+    }
+}
+
+impl Default for NameTemplate {
+    fn default() -> Self {
+        Self(String::from("{project}-{service}-{index}"))
+    }
+}
+
+impl std::str::FromStr for NameTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(template: &str) -> anyhow::Result<Self> {
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                anyhow::bail!("unclosed placeholder in the name template '{template}'");
+            };
+            let placeholder = &rest[start + 1..start + len];
+            if !Self::PLACEHOLDERS.contains(&placeholder) {
+                anyhow::bail!(
+                    "unknown placeholder {{{placeholder}}} in the name template '{template}', \
+                    expected one of {{project}}, {{service}} or {{index}}"
+                );
+            }
+            rest = &rest[start + len + 1..];
+        }
+        // Without the service, every container would get the same name.
+        if !template.contains("{service}") {
+            anyhow::bail!("the name template '{template}' must contain {{service}}");
+        }
+        Ok(Self(template.to_string()))
+    }
+}
+
+/// The service name patterns given on the command line.
+///
+/// A pattern containing `*` or `?` is a glob matched against the whole name, anything else
+/// matches as a substring. Patterns starting with `!` exclude the services they match.
+#[derive(Debug, Default, Clone)]
+pub struct ServiceFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl ServiceFilter {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        let mut filter = Self::default();
+        for pattern in patterns.iter().map(|p| p.as_ref().trim()) {
+            match pattern.strip_prefix('!') {
+                Some(negated) if !negated.is_empty() => filter.exclude.push(negated.to_string()),
+                Some(_) => {}
+                None if !pattern.is_empty() => filter.include.push(pattern.to_string()),
+                None => {}
+            }
+        }
+        filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Whether the service matches any of the included patterns, if there's any,
+    /// and none of the excluded ones.
+    pub fn matches(&self, service: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| pattern_matches(p, service)))
+            && !self.exclude.iter().any(|p| pattern_matches(p, service))
+    }
+}
+
+fn pattern_matches(pattern: &str, name: &str) -> bool {
+    if pattern.contains(['*', '?']) {
+        glob_matches(pattern.as_bytes(), name.as_bytes())
+    } else {
+        name.contains(pattern)
+    }
+}
+
+/// Matches `*` (any run of characters) and `?` (a single character), with backtracking to the
+/// last star. Service names are ASCII, so matching bytes is enough.
+fn glob_matches(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str, name: &str) -> bool {
+        glob_matches(pattern.as_bytes(), name.as_bytes())
+    }
+
+    #[test]
+    fn glob_matches_stars_and_question_marks() {
+        assert!(glob("*", ""));
+        assert!(glob("*", "web"));
+        assert!(glob("w?b", "web"));
+        assert!(!glob("w?b", "wb"));
+        assert!(glob("*-worker", "mail-worker"));
+        assert!(glob("a*b*c", "aXbYbZc"));
+        assert!(!glob("a*b*c", "aXbYbZ"));
+    }
+
+    #[test]
+    fn glob_matches_a_trailing_star() {
+        assert!(glob("api*", "api"));
+        assert!(glob("api*", "api-gateway"));
+        assert!(glob("api**", "api"));
+        assert!(!glob("api*", "my-api"));
+    }
+
+    #[test]
+    fn patterns_without_wildcards_match_substrings() {
+        let filter = ServiceFilter::new(&["api"]);
+        assert!(filter.matches("api"));
+        assert!(filter.matches("my-api-gateway"));
+        assert!(!filter.matches("web"));
+
+        // A glob matches the whole name instead.
+        let filter = ServiceFilter::new(&["api?"]);
+        assert!(filter.matches("api2"));
+        assert!(!filter.matches("my-api2"));
+    }
+
+    #[test]
+    fn a_bare_exclamation_mark_is_ignored() {
+        let filter = ServiceFilter::new(&["!", " ", ""]);
+        assert!(filter.is_empty());
+        assert!(filter.matches("web"));
+    }
+
+    #[test]
+    fn normalize_project_name_as_compose_does() {
+        assert_eq!(normalize_project_name("MyStack"), "mystack");
+        assert_eq!(normalize_project_name("my.stack 2"), "mystack2");
+        assert_eq!(normalize_project_name("-_web-app_1"), "web-app_1");
+        assert_eq!(normalize_project_name("..."), "");
+    }
+
+    #[test]
+    fn load_rejects_a_project_name_normalized_to_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_dir = dir.path().join("__");
+        std::fs::create_dir(&project_dir).unwrap();
+        let file = project_dir.join("docker-compose.yml");
+        std::fs::write(&file, "services:\n  web:\n    image: nginx\n").unwrap();
+
+        let error = ProjectFile::load(&file.to_string_lossy()).err().unwrap();
+        assert!(error.to_string().contains("COMPOSE_PROJECT_NAME"));
+    }
+
+    #[test]
+    fn includes_and_excludes_combine() {
+        let filter = ServiceFilter::new(&["api*", "!*-test", "db"]);
+        assert!(filter.matches("api"));
+        assert!(filter.matches("api-gateway"));
+        assert!(filter.matches("db"));
+        assert!(!filter.matches("api-test"));
+        assert!(!filter.matches("web"));
+
+        // Only excluding keeps everything else.
+        let filter = ServiceFilter::new(&["!db"]);
+        assert!(filter.matches("web"));
+        assert!(!filter.matches("db"));
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+
+/// The number of compose files kept in the history.
+const MAX_RECENT: usize = 10;
+
+/// The directory of dcr's persistent state, respecting `XDG_STATE_HOME`.
+pub(crate) fn state_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("state"))
+        })
+        .map(|dir| dir.join("dcr"))
+}
+
+fn history_file() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join("recent"))
+}
+
+/// The recently opened compose files that still exist, the most recent first.
+pub fn load() -> Vec<PathBuf> {
+    let Some(Ok(content)) = history_file().map(std::fs::read_to_string) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .map(PathBuf::from)
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// The most recently opened compose file, if there's any.
+pub fn last() -> Option<PathBuf> {
+    load().into_iter().next()
+}
+
+/// Moves the compose file to the front of the history.
+pub fn record(file: &Path) -> anyhow::Result<()> {
+    let Some(history_file) = history_file() else {
+        return Ok(());
+    };
+    let file = file.canonicalize()?;
+    let mut recent = load();
+    recent.retain(|path| path != &file);
+    recent.insert(0, file);
+    recent.truncate(MAX_RECENT);
+
+    if let Some(dir) = history_file.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create the state directory {}", dir.display()))?;
+    }
+    let content = recent
+        .iter()
+        .map(|path| format!("{}\n", path.display()))
+        .collect::<String>();
+    std::fs::write(&history_file, content)
+        .with_context(|| format!("Failed to write {}", history_file.display()))
+}
+
+/// Lists the recently opened compose files and asks which one to open.
+///
+/// Runs before the terminal interface starts, so it just uses the standard streams.
+pub fn pick() -> anyhow::Result<Option<PathBuf>> {
+    let recent = load();
+    if recent.is_empty() {
+        eprintln!("No recently opened compose files.");
+        return Ok(None);
+    }
+    let mut stderr = std::io::stderr();
+    for (i, path) in recent.iter().enumerate() {
+        writeln!(stderr, "({}) {}", i + 1, path.display())?;
+    }
+    write!(stderr, "Open [1]: ")?;
+    stderr.flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    let answer = answer.trim();
+    let choice = if answer.is_empty() {
+        0
+    } else {
+        answer
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .filter(|n| *n < recent.len())
+            .with_context(|| format!("Invalid choice: {answer}"))?
+    };
+    Ok(recent.into_iter().nth(choice))
+}
@@ -0,0 +1,99 @@
+//! Compose files given as an `http(s)://` URL.
+//!
+//! The file is downloaded into a temporary file, since `docker compose` needs a local path.
+//! Keep in mind that a compose file can mount any host path and run any image, so only open
+//! the files you'd also run by hand.
+
+use std::{
+    io::Read as _,
+    path::{Path, PathBuf},
+};
+
+use crate::project::normalize_project_name;
+
+/// The name of the downloaded file when the URL doesn't end with one.
+const DEFAULT_NAME: &str = "docker-compose.yml";
+/// The largest compose file downloaded, anything above is unlikely to be a compose file.
+const MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Whether the compose file is given as a URL.
+pub fn is_url(file: &str) -> bool {
+    file.starts_with("http://") || file.starts_with("https://")
+}
+
+/// A downloaded compose file, its directory is removed when it's dropped.
+pub struct RemoteFile {
+    _dir: tempfile::TempDir,
+    path: PathBuf,
+}
+
+impl RemoteFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Downloads the compose file into the temporary directory.
+pub fn fetch(url: &str) -> anyhow::Result<RemoteFile> {
+    let report = |reason: String| {
+        let report = miette::miette!(
+            help = "check that the URL points to the raw compose file and that it's reachable",
+            "Failed to fetch the compose file from {url}: {reason}"
+        );
+        anyhow::anyhow!("{report:?}")
+    };
+    let response = ureq::get(url).call().map_err(|e| report(e.to_string()))?;
+    let mut content = String::new();
+    // One byte over the limit is enough to tell that the file is too large.
+    response
+        .into_reader()
+        .take(MAX_SIZE + 1)
+        .read_to_string(&mut content)
+        .map_err(|e| report(e.to_string()))?;
+    if content.len() as u64 > MAX_SIZE {
+        return Err(report(format!(
+            "the file is larger than {} MiB",
+            MAX_SIZE / 1024 / 1024
+        )));
+    }
+
+    let name = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|url| url.rsplit('/').next())
+        .filter(|name| name.ends_with(".yml") || name.ends_with(".yaml"))
+        .unwrap_or(DEFAULT_NAME);
+    // A fresh directory only the owner can access, a predictable path could be prepared by another user.
+    // It's removed when the session ends, on whichever path it ends.
+    let dir = tempfile::Builder::new()
+        .prefix("dcr-")
+        .tempdir()
+        .map_err(|e| report(e.to_string()))?;
+    let path = dir.path().join(name);
+    std::fs::write(&path, content).map_err(|e| report(e.to_string()))?;
+    Ok(RemoteFile { _dir: dir, path })
+}
+
+/// Loads the `.env` of the working directory, and names the project after it unless it's named already.
+///
+/// The downloaded file lives in a temporary directory, which would otherwise give the project its name.
+pub fn use_working_directory() -> anyhow::Result<()> {
+    let cwd = std::env::current_dir()?;
+    dotenvy::from_path(cwd.join(".env")).ok();
+    if std::env::var_os("COMPOSE_PROJECT_NAME").is_none() {
+        if let Some(name) = cwd.file_name() {
+            // Inherited by the compose commands as well, so they agree on the project.
+            // Compose rejects the names that aren't normalized, like `My.Stack`.
+            let name = name.to_string_lossy();
+            let normalized = normalize_project_name(&name);
+            if normalized.is_empty() {
+                anyhow::bail!(
+                    "the working directory `{name}` has no letters or digits to name the project after, \
+                     set COMPOSE_PROJECT_NAME to name it"
+                );
+            }
+            std::env::set_var("COMPOSE_PROJECT_NAME", normalized);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use bollard::{
+    container::{Stats, StatsOptions},
+    models::HostConfig,
+};
+use futures::StreamExt;
+use indexmap::IndexMap;
+use tokio::task::JoinHandle;
+
+/// The number of samples kept per service, about a minute as Docker sends one every second.
+pub const HISTORY_LEN: usize = 60;
+
+/// The resource usage of a container at one point in time.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Sample {
+    /// The CPU usage in percent of a single core, so it can go above 100 on multiple cores.
+    pub cpu_percent: f64,
+    /// The memory usage in bytes.
+    pub memory: u64,
+}
+
+impl Sample {
+    /// Calculates the usage the same way `docker stats` does.
+    pub fn from_stats(stats: &Stats) -> Self {
+        let cpu_delta = stats
+            .cpu_stats
+            .cpu_usage
+            .total_usage
+            .saturating_sub(stats.precpu_stats.cpu_usage.total_usage);
+        let system_delta = stats
+            .cpu_stats
+            .system_cpu_usage
+            .unwrap_or_default()
+            .saturating_sub(stats.precpu_stats.system_cpu_usage.unwrap_or_default());
+        let online_cpus = stats.cpu_stats.online_cpus.unwrap_or_else(|| {
+            stats
+                .cpu_stats
+                .cpu_usage
+                .percpu_usage
+                .as_ref()
+                .map_or(1, |usage| usage.len() as u64)
+        });
+        let cpu_percent = if system_delta > 0 {
+            cpu_delta as f64 / system_delta as f64 * online_cpus as f64 * 100.0
+        } else {
+            0.0
+        };
+        Self {
+            cpu_percent,
+            memory: stats.memory_stats.usage.unwrap_or_default(),
+        }
+    }
+}
+
+/// The recent samples of each service, capped at [`HISTORY_LEN`].
+#[derive(Debug, Clone, Default)]
+pub struct StatsHistory(Arc<Mutex<IndexMap<usize, VecDeque<Sample>>>>);
+
+impl StatsHistory {
+    pub fn push(&self, idx: usize, sample: Sample) {
+        let mut history = self.0.lock().unwrap();
+        let samples = history.entry(idx).or_default();
+        if samples.len() == HISTORY_LEN {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// The samples of the service, the oldest first.
+    pub fn samples(&self, idx: usize) -> Vec<Sample> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(&idx)
+            .map(|samples| samples.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+/// The latest CPU percent and memory bytes of each running service, shared with their streams.
+pub type ContainerStats = Arc<Mutex<IndexMap<usize, (f64, u64)>>>;
+
+/// Streams the latest resource usage of the container into the map, until the container stops.
+/// The entry is removed then, so a stopped container has no numbers.
+pub fn spawn_usage_stream(
+    idx: usize,
+    container_name: &str,
+    docker: &bollard::Docker,
+    container_stats: ContainerStats,
+) -> JoinHandle<()> {
+    let mut stream = docker.stats(
+        container_name,
+        Some(StatsOptions {
+            stream: true,
+            one_shot: false,
+        }),
+    );
+    tokio::spawn(async move {
+        while let Some(Ok(stats)) = stream.next().await {
+            let sample = Sample::from_stats(&stats);
+            container_stats
+                .lock()
+                .unwrap()
+                .insert(idx, (sample.cpu_percent, sample.memory));
+        }
+        container_stats.lock().unwrap().shift_remove(&idx);
+    })
+}
+
+/// Streams the resource usage of the container into the history until the container stops.
+pub fn spawn_stats_stream(
+    idx: usize,
+    container_name: &str,
+    docker: &bollard::Docker,
+    history: StatsHistory,
+) -> JoinHandle<()> {
+    let mut stream = docker.stats(
+        container_name,
+        Some(StatsOptions {
+            stream: true,
+            one_shot: false,
+        }),
+    );
+    tokio::spawn(async move {
+        while let Some(Ok(stats)) = stream.next().await {
+            history.push(idx, Sample::from_stats(&stats));
+        }
+    })
+}
+
+/// The limit shown for the unset fields.
+const UNLIMITED: &str = "unlimited";
+
+/// The resource limits the container was created with, as (name, value) pairs.
+///
+/// Docker reports the unset limits as missing or zero, and some of them as -1.
+pub fn configured_limits(host_config: Option<&HostConfig>) -> Vec<(&'static str, String)> {
+    let set = |value: Option<i64>| value.filter(|value| *value > 0);
+    let host_config = host_config.cloned().unwrap_or_default();
+    let bytes = |value: Option<i64>| {
+        set(value).map_or_else(|| UNLIMITED.to_string(), |v| format_bytes(v as u64))
+    };
+    let cpus = match (
+        set(host_config.nano_cpus),
+        set(host_config.cpu_quota),
+        set(host_config.cpu_period),
+    ) {
+        (Some(nano_cpus), _, _) => format!("{:.2}", nano_cpus as f64 / 1e9),
+        // The default period is 100ms.
+        (None, Some(quota), period) => {
+            format!("{:.2}", quota as f64 / period.unwrap_or(100_000) as f64)
+        }
+        (None, None, _) => UNLIMITED.to_string(),
+    };
+    vec![
+        ("memory", bytes(host_config.memory)),
+        ("memory + swap", bytes(host_config.memory_swap)),
+        ("cpus", cpus),
+        (
+            "pids",
+            set(host_config.pids_limit)
+                .map_or_else(|| UNLIMITED.to_string(), |pids| pids.to_string()),
+        ),
+    ]
+}
+
+/// Formats the bytes with a binary unit, like `12.3 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
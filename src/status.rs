@@ -0,0 +1,138 @@
+use unicode_width::UnicodeWidthStr as _;
+
+use crate::app::App;
+
+/// How the status of the stack is printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum StatusFormat {
+    /// A single JSON object.
+    #[default]
+    Json,
+    /// The same structure as the JSON, in YAML.
+    Yaml,
+    /// An aligned text table, one service per row.
+    Table,
+}
+
+/// The state of a service's container at the time of the snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceStatus {
+    pub service: String,
+    pub container: String,
+    /// The container state like `running` or `exited`, `not created` if there's no container.
+    pub state: String,
+    pub health: Option<String>,
+    /// Only set for the containers that exited.
+    pub exit_code: Option<i64>,
+    /// Only set for the running containers.
+    pub started_at: Option<String>,
+}
+
+/// The status of every service of the active project.
+pub fn snapshot(app: &App) -> Vec<ServiceStatus> {
+    app.compose_content
+        .compose
+        .services
+        .0
+        .keys()
+        .enumerate()
+        .map(|(i, service)| {
+            let state = app
+                .container_info
+                .get(&i)
+                .and_then(|info| info.as_ref()?.state.clone());
+            let status = state.as_ref().and_then(|state| state.status);
+            let running = state.as_ref().and_then(|state| state.running) == Some(true);
+            ServiceStatus {
+                service: service.clone(),
+                container: app
+                    .container_name_mapping
+                    .get(&i)
+                    .cloned()
+                    .unwrap_or_default(),
+                state: status.map_or_else(|| String::from("not created"), |s| s.to_string()),
+                health: state
+                    .as_ref()
+                    .and_then(|state| state.health.as_ref()?.status)
+                    .map(|health| health.to_string()),
+                exit_code: state
+                    .as_ref()
+                    .filter(|_| !running && status.is_some())
+                    .and_then(|state| state.exit_code),
+                started_at: state
+                    .as_ref()
+                    .filter(|_| running)
+                    .and_then(|state| state.started_at.clone()),
+            }
+        })
+        .collect()
+}
+
+/// The snapshot of the active project in the given format, ending with a newline.
+pub fn render(app: &App, format: StatusFormat) -> anyhow::Result<String> {
+    let services = snapshot(app);
+    if format == StatusFormat::Table {
+        return Ok(table(&services));
+    }
+    let value = serde_json::json!({
+        "project": app.project_name,
+        "file": app.full_path,
+        "services": services
+            .iter()
+            .map(|status| serde_json::json!({
+                "service": status.service,
+                "container": status.container,
+                "state": status.state,
+                "health": status.health,
+                "exit_code": status.exit_code,
+                "started_at": status.started_at,
+            }))
+            .collect::<Vec<_>>(),
+    });
+    Ok(match format {
+        StatusFormat::Json => format!("{}\n", serde_json::to_string_pretty(&value)?),
+        StatusFormat::Yaml => serde_yaml::to_string(&value)?,
+        StatusFormat::Table => unreachable!("rendered above"),
+    })
+}
+
+/// The statuses as a table with a header, the columns padded to their widest value.
+fn table(services: &[ServiceStatus]) -> String {
+    let header = [
+        "SERVICE",
+        "CONTAINER",
+        "STATE",
+        "HEALTH",
+        "EXIT CODE",
+        "STARTED AT",
+    ];
+    let mut rows = vec![header.map(String::from)];
+    rows.extend(services.iter().map(|status| {
+        [
+            status.service.clone(),
+            status.container.clone(),
+            status.state.clone(),
+            status.health.clone().unwrap_or_else(|| String::from("-")),
+            status
+                .exit_code
+                .map_or_else(|| String::from("-"), |code| code.to_string()),
+            status
+                .started_at
+                .clone()
+                .unwrap_or_else(|| String::from("-")),
+        ]
+    }));
+    let widths: [usize; 6] =
+        std::array::from_fn(|col| rows.iter().map(|row| row[col].width()).max().unwrap_or(0));
+    rows.iter()
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell}{}", " ".repeat(width - cell.width())))
+                .collect::<Vec<_>>()
+                .join("  ");
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
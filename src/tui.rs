@@ -51,10 +51,24 @@ impl<B: Backend> Tui<B> {
     /// [`Draw`]: ratatui::Terminal::draw
     /// [`rendering`]: crate::ui::render
     pub fn draw(&mut self, app: &mut App) -> anyhow::Result<()> {
+        if std::mem::take(&mut app.redraw) {
+            self.repaint()?;
+        }
         self.terminal.draw(|frame| ui::render(app, frame))?;
         Ok(())
     }
 
+    /// Restores the terminal properties and clears the screen, so the next draw paints everything again.
+    ///
+    /// Recovers from another process writing to the terminal or switching it out of the alternate screen.
+    fn repaint(&mut self) -> anyhow::Result<()> {
+        terminal::enable_raw_mode()?;
+        crossterm::execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
+        self.terminal.hide_cursor()?;
+        self.terminal.clear()?;
+        Ok(())
+    }
+
     /// Resets the terminal interface.
     ///
     /// This function is also used for the panic hook to revert
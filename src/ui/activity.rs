@@ -0,0 +1,65 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr as _;
+
+use super::panel::render_panel;
+use crate::app::{ActivityFilter, App};
+
+/// The number of the most recent entries shown.
+const ACTIVITY_ENTRIES: usize = 500;
+
+/// The lifecycle events and the logs of the whole project, the newest first so it follows itself.
+pub fn render_activity(app: &mut App, frame: &mut Frame) {
+    let activity = app.activity();
+    let name_width = app
+        .compose_content
+        .compose
+        .services
+        .0
+        .keys()
+        .map(|name| name.width())
+        .max()
+        .unwrap_or_default();
+    let time_style = Style::default().fg(Color::DarkGray);
+    let event_style = Style::default().fg(Color::Yellow);
+    let log_style = Style::default().fg(Color::Gray);
+    let tz = jiff::tz::TimeZone::system();
+
+    let mut lines = activity
+        .iter()
+        .rev()
+        .take(ACTIVITY_ENTRIES)
+        .map(|entry| {
+            let time = entry.time.to_zoned(tz.clone()).strftime("%H:%M:%S");
+            let (kind, style) = if entry.event {
+                ("event", event_style)
+            } else {
+                ("log  ", log_style)
+            };
+            Line::from(vec![
+                Span::styled(format!("{time} "), time_style),
+                Span::raw(format!("{:name_width$} ", entry.service)),
+                Span::styled(format!("[{kind}] "), style),
+                Span::styled(entry.text.clone(), style),
+            ])
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        lines.push(Line::styled("Nothing happened yet.", time_style));
+    }
+
+    let showing = match app.activity_filter {
+        ActivityFilter::Both => "events and logs",
+        ActivityFilter::Events => "events",
+        ActivityFilter::Logs => "logs",
+    };
+    render_panel(
+        app,
+        frame,
+        &format!("Activity: {showing}, newest first ((tab) filter)"),
+        lines,
+    );
+}
@@ -0,0 +1,102 @@
+use docker_compose_types::BuildStep;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+use super::panel::render_panel;
+use crate::app::App;
+
+/// The Dockerfile compose looks for in the build context when none is given.
+const DEFAULT_DOCKERFILE: &str = "Dockerfile";
+
+pub fn render_build(app: &mut App, frame: &mut Frame) {
+    let selected = app.compose_content.state.selected().unwrap_or(0);
+    let service = app
+        .compose_content
+        .compose
+        .services
+        .0
+        .get_index(selected)
+        .and_then(|(_, service)| service.clone())
+        .unwrap_or_default();
+
+    let key_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let value_style = Style::default().fg(Color::LightYellow);
+    let detail_style = Style::default().fg(Color::Gray);
+    let entry = |key: &'static str, value: String, detail: Option<String>| {
+        let mut spans = vec![
+            Span::styled(key, key_style),
+            Span::styled(value, value_style),
+        ];
+        if let Some(detail) = detail {
+            spans.push(Span::styled(format!("  ({detail})"), detail_style));
+        }
+        Line::from(spans)
+    };
+
+    let (context, dockerfile, target) = match service.build_ {
+        Some(BuildStep::Simple(context)) => (context, None, None),
+        Some(BuildStep::Advanced(build)) => (build.context, build.dockerfile, build.target),
+        None => {
+            let lines = vec![
+                Line::styled("(image-based)", detail_style),
+                entry(
+                    "image: ",
+                    service
+                        .image
+                        .unwrap_or_else(|| String::from("<unspecified>")),
+                    None,
+                ),
+            ];
+            render_panel(app, frame, "Build", lines);
+            return;
+        }
+    };
+
+    // Relative paths are resolved from the directory of the compose file.
+    let project_dir = app.full_path.parent().unwrap_or(&app.full_path);
+    let context_path = project_dir.join(&context);
+    let (dockerfile, dockerfile_detail) = match dockerfile {
+        Some(dockerfile) => (dockerfile, None),
+        None => (
+            String::from(DEFAULT_DOCKERFILE),
+            Some(String::from("default")),
+        ),
+    };
+    let dockerfile_path = context_path.join(&dockerfile);
+
+    let remote = context.contains("://") || context.starts_with("git@");
+    let mut lines = vec![
+        entry(
+            "context: ",
+            context,
+            Some(context_path.display().to_string()),
+        ),
+        entry(
+            "dockerfile: ",
+            dockerfile,
+            Some(
+                dockerfile_detail
+                    .map(|detail| format!("{detail}, {}", dockerfile_path.display()))
+                    .unwrap_or_else(|| dockerfile_path.display().to_string()),
+            ),
+        ),
+    ];
+    if let Some(target) = target {
+        lines.push(entry("target: ", target, None));
+    }
+    // Remote contexts like git repositories are not checked.
+    if !remote && !dockerfile_path.is_file() {
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            format!("{} doesn't exist.", dockerfile_path.display()),
+            Style::default().fg(Color::Red),
+        ));
+    }
+
+    render_panel(app, frame, "Build", lines);
+}
@@ -0,0 +1,174 @@
+use ratatui::{
+    layout::Constraint,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Row, Table},
+    Frame,
+};
+use ratatui_macros::vertical;
+use serde_json::Value;
+
+use super::{get_bg_color, legend::create_container_info, panel::render_panel, render_scrollbar};
+use crate::app::App;
+
+const TITLE: &str = "docker compose ps ((f) to refresh)";
+const COLUMNS: [&str; 6] = ["NAME", "SERVICE", "COMMAND", "STATE", "STATUS", "PORTS"];
+
+pub fn render_compose_ps(app: &mut App, frame: &mut Frame) {
+    let rows = match &app.compose_ps {
+        Ok(output) => parse_ps(output),
+        Err(error) => {
+            let lines = error
+                .lines()
+                .map(|line| Line::from(line.to_string()))
+                .collect();
+            render_panel(app, frame, TITLE, lines);
+            return;
+        }
+    };
+    // Older compose versions don't know the json format, the raw output is still better than nothing.
+    let Some(rows) = rows else {
+        let lines = app
+            .compose_ps
+            .as_deref()
+            .unwrap_or_default()
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect();
+        render_panel(app, frame, TITLE, lines);
+        return;
+    };
+
+    let bg = get_bg_color();
+    let [header, main] = vertical![==3, >=1].areas(frame.area());
+    let screen = &mut app.alternate_screen;
+    screen.panel_scroll = screen.panel_scroll.min(rows.len().saturating_sub(1));
+    screen.panel_scroll_state = screen
+        .panel_scroll_state
+        .viewport_content_length(20)
+        .content_length(rows.len())
+        .position(screen.panel_scroll);
+
+    // Every column is as wide as its widest cell, except the last one which takes the rest.
+    let widths = (0..COLUMNS.len())
+        .map(|i| {
+            let widest = rows
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([COLUMNS[i].len()])
+                .max()
+                .unwrap_or_default();
+            if i == COLUMNS.len() - 1 {
+                Constraint::Min(widest as u16)
+            } else {
+                Constraint::Length(widest as u16)
+            }
+        })
+        .collect::<Vec<_>>();
+    let state_style = |state: &str| match state {
+        "running" => Style::default().fg(Color::LightGreen),
+        "exited" | "dead" => Style::default().fg(Color::Red),
+        _ => Style::default().fg(Color::Yellow),
+    };
+    let table_rows = rows
+        .iter()
+        .skip(screen.panel_scroll)
+        .map(|row| Row::new(row.clone()).style(state_style(&row[3])))
+        .collect::<Vec<_>>();
+    let empty = table_rows.is_empty();
+
+    frame.render_widget(
+        Table::new(table_rows, widths)
+            .header(
+                Row::new(COLUMNS).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .column_spacing(2)
+            .block(
+                Block::default()
+                    .title(if empty {
+                        format!("{TITLE} - no containers")
+                    } else {
+                        String::from(TITLE)
+                    })
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::LightBlue).bg(bg)),
+            ),
+        main,
+    );
+    render_scrollbar(
+        frame,
+        main,
+        &mut app.alternate_screen.panel_scroll_state,
+        app.show_scrollbars,
+    );
+    frame.render_widget(create_container_info(app, header.width), header);
+}
+
+/// Parses the json output of `docker compose ps` into the table cells.
+///
+/// Newer compose versions print a json object per line, older ones a single array.
+fn parse_ps(output: &str) -> Option<Vec<[String; 6]>> {
+    let output = output.trim();
+    let containers = if output.is_empty() {
+        Vec::new()
+    } else if output.starts_with('[') {
+        serde_json::from_str::<Vec<Value>>(output).ok()?
+    } else {
+        output
+            .lines()
+            .map(serde_json::from_str::<Value>)
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?
+    };
+
+    let field = |container: &Value, key: &str| {
+        container[key]
+            .as_str()
+            .map(String::from)
+            .unwrap_or_default()
+    };
+    let rows = containers
+        .iter()
+        .map(|container| {
+            let ports = match container["Ports"].as_str() {
+                Some(ports) => ports.to_string(),
+                None => publishers(container),
+            };
+            [
+                field(container, "Name"),
+                field(container, "Service"),
+                field(container, "Command"),
+                field(container, "State"),
+                field(container, "Status"),
+                ports,
+            ]
+        })
+        .collect();
+    Some(rows)
+}
+
+/// Formats the published ports of the container like `docker ps` does.
+fn publishers(container: &Value) -> String {
+    let Some(publishers) = container["Publishers"].as_array() else {
+        return String::new();
+    };
+    publishers
+        .iter()
+        .map(|publisher| {
+            let target = &publisher["TargetPort"];
+            let protocol = publisher["Protocol"].as_str().unwrap_or("tcp");
+            match publisher["PublishedPort"].as_u64() {
+                Some(published) if published > 0 => {
+                    let url = publisher["URL"].as_str().unwrap_or_default();
+                    format!("{url}:{published}->{target}/{protocol}")
+                }
+                _ => format!("{target}/{protocol}"),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,23 +1,39 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Margin},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation},
+    widgets::{Block, Borders, Paragraph, Sparkline},
     Frame,
 };
 use ratatui_macros::{horizontal, vertical};
 
-use super::{get_bg_color, legend::create_container_info, ALL_INTERFACES, UNNAMED, UNSPECIFIED};
-use crate::{app::App, handler::SplitScreen};
+use super::{
+    get_bg_color,
+    legend::{create_container_info, create_restart_policy, restart_policy_width},
+    render_scrollbar, ALL_INTERFACES, UNNAMED, UNSPECIFIED,
+};
+use crate::{
+    app::App,
+    diff::port_exposure,
+    handler::{AlternateScreenContent, SplitScreen},
+    stats::{configured_limits, format_bytes},
+};
+
+/// The number of log lines to show at the bottom of the details screen.
+const LOG_TAIL_LINES: usize = 2;
+/// The number of the entries of a bind mount listed, the rest are only counted.
+const MOUNT_LISTING_ENTRIES: usize = 20;
+/// Below this width the quadrants are too thin side by side, so they're stacked in one column.
+const STACKED_COLS: u16 = 140;
 
 pub fn render_container_details(app: &mut App, frame: &mut Frame, i: SplitScreen) {
     let bg = get_bg_color();
     let size = frame.area();
-    let selected = app
-        .compose_content
-        .state
-        .selected()
-        .expect("a valid selection");
+    // The service filter may have hidden every service meanwhile.
+    let Some(selected) = app.compose_content.state.selected() else {
+        app.alternate_screen_content = AlternateScreenContent::None;
+        return;
+    };
     let Some(Some(container_info)) = app.container_info.get(&selected) else {
         let name = app.container_name_mapping.get(&selected).expect("to exist");
         frame.render_widget(
@@ -41,15 +57,10 @@ pub fn render_container_details(app: &mut App, frame: &mut Frame, i: SplitScreen
         .and_then(|cfg| cfg.env.as_deref())
         .unwrap_or_default();
 
-    let labels = container_info
-        .config
-        .as_ref()
-        .and_then(|cfg| cfg.labels.clone())
-        .unwrap_or_default();
-
-    let labels_formatted: Vec<_> = labels
+    let labels_formatted: Vec<_> = app
+        .label_entries()
         .into_iter()
-        .map(|(name, value)| format!("{name}: {value}"))
+        .map(|(_, entry)| entry)
         .collect();
 
     let volumes = container_info
@@ -60,14 +71,32 @@ pub fn render_container_details(app: &mut App, frame: &mut Frame, i: SplitScreen
                 .iter()
                 .enumerate()
                 .map(|(i, mount)| {
-                    format!(
+                    let mut entry = format!(
                         "{}:\n name: {}\n source: {}\n destination: {}\n driver: {}",
                         i + 1,
                         mount.name.as_deref().unwrap_or(UNNAMED),
                         mount.source.as_deref().unwrap_or_default(),
                         mount.destination.as_deref().unwrap_or_default(),
                         mount.driver.as_deref().unwrap_or(UNSPECIFIED),
-                    )
+                    );
+                    if let Some((_, _, listing)) = app
+                        .alternate_screen
+                        .mount_listing
+                        .as_ref()
+                        .filter(|(service, mount, _)| (*service, *mount) == (selected, i))
+                    {
+                        entry.push_str(&format!("\n contents ({} entries):", listing.len()));
+                        for name in listing.iter().take(MOUNT_LISTING_ENTRIES) {
+                            entry.push_str(&format!("\n  {name}"));
+                        }
+                        if listing.len() > MOUNT_LISTING_ENTRIES {
+                            entry.push_str(&format!(
+                                "\n  .. and {} more",
+                                listing.len() - MOUNT_LISTING_ENTRIES
+                            ));
+                        }
+                    }
+                    entry
                 })
                 .collect::<Vec<_>>()
         })
@@ -133,14 +162,53 @@ pub fn render_container_details(app: &mut App, frame: &mut Frame, i: SplitScreen
         .unwrap_or_default();
     networks.extend(network_settings);
 
+    let service = app
+        .compose_content
+        .compose
+        .services
+        .0
+        .get_index(selected)
+        .and_then(|(_, service)| service.clone())
+        .unwrap_or_default();
+    let exposure = port_exposure(&service, container_info);
+    if !exposure.is_empty() {
+        networks.push(String::from(
+            "Exposed by the image vs published in the file:",
+        ));
+        networks.extend(exposure.iter().map(|port| {
+            let marker = if port.is_mismatch() { "!" } else { " " };
+            let exposed = if port.exposed {
+                "exposed"
+            } else {
+                "not exposed"
+            };
+            let published = if port.published.is_empty() {
+                String::from("not published")
+            } else {
+                format!("published on {}", port.published.join(", "))
+            };
+            format!("{marker}{}: {exposed}, {published}", port.port)
+        }));
+    }
+
     let header_and_main = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(1)])
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(3),
+            Constraint::Length(LOG_TAIL_LINES as u16 + 2),
+        ])
         .split(size);
 
-    let [upper_area, lower_area] = vertical![== 50%, == 50%].areas(header_and_main[1]);
-    let [upper_left, upper_right] = horizontal![== 50%, == 50%].areas(upper_area);
-    let [lower_left, lower_right] = horizontal![== 50%, == 50%].areas(lower_area);
+    let [upper_left, lower_left, upper_right, lower_right] = if size.width < STACKED_COLS {
+        stacked_areas(header_and_main[1], i)
+    } else {
+        let [upper_area, lower_area] = vertical![== 50%, == 50%].areas(header_and_main[1]);
+        let [upper_left, upper_right] = horizontal![== 50%, == 50%].areas(upper_area);
+        let [lower_left, lower_right] = horizontal![== 50%, == 50%].areas(lower_area);
+        [upper_left, lower_left, upper_right, lower_right]
+    };
 
     let style_selected = Style::default().fg(Color::Red).bg(bg);
     let style_not_selected = Style::default().fg(Color::LightBlue).bg(bg);
@@ -171,156 +239,289 @@ pub fn render_container_details(app: &mut App, frame: &mut Frame, i: SplitScreen
         ),
     };
 
-    app.alternate_screen.lower_left_scroll_state = app
-        .alternate_screen
+    // Only the visible slice is wrapped and rendered, so huge env or label sets stay cheap.
+    // The scroll position is measured in entries.
+    let screen = &mut app.alternate_screen;
+    screen.lower_left_scroll = screen.lower_left_scroll.min(env.len().saturating_sub(1));
+    screen.lower_left_scroll_state = screen
         .lower_left_scroll_state
         .viewport_content_length(20)
-        .content_length(env.len());
-    app.alternate_screen.upper_left_scroll_state = app
-        .alternate_screen
+        .content_length(env.len())
+        .position(screen.lower_left_scroll);
+    screen.upper_left_scroll = screen
+        .upper_left_scroll
+        .min(labels_formatted.len().saturating_sub(1));
+    screen.upper_left_scroll_state = screen
         .upper_left_scroll_state
         .viewport_content_length(20)
-        .content_length(labels_formatted.len());
-    app.alternate_screen.lower_right_scroll_state = app
-        .alternate_screen
+        .content_length(labels_formatted.len())
+        .position(screen.upper_left_scroll);
+    screen.lower_right_scroll = screen
+        .lower_right_scroll
+        .min(networks.len().saturating_sub(1));
+    screen.lower_right_scroll_state = screen
         .lower_right_scroll_state
         .viewport_content_length(20)
-        .content_length(networks.len());
-    app.alternate_screen.upper_right_scroll_state = app
-        .alternate_screen
+        .content_length(networks.len())
+        .position(screen.lower_right_scroll);
+    screen.upper_right_scroll = screen
+        .upper_right_scroll
+        .min(volumes.len().saturating_sub(1));
+    screen.upper_right_scroll_state = screen
         .upper_right_scroll_state
         .viewport_content_length(20)
-        .content_length(volumes.len());
+        .content_length(volumes.len())
+        .position(screen.upper_right_scroll);
 
-    let networks = Text::from(
-        textwrap::wrap(
-            &networks.join("\n"),
-            textwrap::Options::new(lower_right.width.saturating_sub(2) as _),
-        )
+    // Without wrapping, only the focused quadrant is scrolled horizontally.
+    let focused: &[String] = match i {
+        SplitScreen::UpperLeft => &labels_formatted,
+        SplitScreen::LowerLeft => env,
+        SplitScreen::UpperRight => &volumes,
+        SplitScreen::LowerRight => &networks,
+    };
+    let max_width = focused
         .iter()
-        .map(|s| Line::from(s.to_string()))
-        .collect::<Vec<_>>(),
+        .flat_map(|entry| entry.lines())
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or_default();
+    screen.horizontal_scroll = screen.horizontal_scroll.min(max_width.saturating_sub(1));
+    let horizontal = |quadrant: SplitScreen| {
+        (!screen.wrap).then_some(if quadrant == i {
+            screen.horizontal_scroll
+        } else {
+            0
+        })
+    };
+
+    let networks = visible_lines(
+        &networks,
+        screen.lower_right_scroll,
+        lower_right,
+        horizontal(SplitScreen::LowerRight),
     );
-    let labels_formatted = Text::from(
-        textwrap::wrap(
-            &labels_formatted.join("\n"),
-            textwrap::Options::new(upper_left.width.saturating_sub(2) as _),
-        )
-        .iter()
-        .map(|s| Line::from(s.to_string()))
-        .collect::<Vec<_>>(),
+    let labels_formatted = visible_lines(
+        &labels_formatted,
+        screen.upper_left_scroll,
+        upper_left,
+        horizontal(SplitScreen::UpperLeft),
     );
-
-    let volumes = Text::from(
-        textwrap::wrap(
-            &volumes.join("\n"),
-            textwrap::Options::new(upper_right.width.saturating_sub(2) as _),
-        )
-        .iter()
-        .map(|s| Line::from(s.to_string()))
-        .collect::<Vec<_>>(),
+    let volumes = visible_lines(
+        &volumes,
+        screen.upper_right_scroll,
+        upper_right,
+        horizontal(SplitScreen::UpperRight),
     );
-
-    let env = Text::from(
-        textwrap::wrap(
-            &env.join("\n"),
-            textwrap::Options::new(lower_left.width.saturating_sub(2) as _),
-        )
-        .iter()
-        .map(|s| Line::from(s.to_string()))
-        .collect::<Vec<_>>(),
+    let env = visible_lines(
+        env,
+        screen.lower_left_scroll,
+        lower_left,
+        horizontal(SplitScreen::LowerLeft),
     );
 
     frame.render_widget(
-        Paragraph::new(env)
-            .scroll((app.alternate_screen.lower_left_scroll as _, 0))
-            .block(
-                Block::default()
-                    .title("Environment variables")
-                    .borders(Borders::ALL)
-                    .style(env_style),
-            ),
+        Paragraph::new(env).block(
+            Block::default()
+                .title("Environment variables")
+                .borders(Borders::ALL)
+                .style(env_style),
+        ),
         lower_left,
     );
     frame.render_widget(
-        Paragraph::new(networks)
-            .scroll((app.alternate_screen.lower_right_scroll as _, 0))
-            .block(
-                Block::default()
-                    .title("Networks")
-                    .borders(Borders::ALL)
-                    .style(network_style),
-            ),
+        Paragraph::new(networks).block(
+            Block::default()
+                .title("Networks")
+                .borders(Borders::ALL)
+                .style(network_style),
+        ),
         lower_right,
     );
 
-    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-        .begin_symbol(Some("↑"))
-        .end_symbol(Some("↓"));
-    frame.render_stateful_widget(
-        scrollbar,
-        lower_left.inner(Margin {
-            vertical: 1,
-            horizontal: 0,
-        }),
+    render_scrollbar(
+        frame,
+        lower_left,
         &mut app.alternate_screen.lower_left_scroll_state,
+        app.show_scrollbars,
     );
-    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-        .begin_symbol(Some("↑"))
-        .end_symbol(Some("↓"));
-    frame.render_stateful_widget(
-        scrollbar,
-        lower_right.inner(Margin {
-            vertical: 1,
-            horizontal: 0,
-        }),
+    render_scrollbar(
+        frame,
+        lower_right,
         &mut app.alternate_screen.lower_right_scroll_state,
+        app.show_scrollbars,
     );
 
     frame.render_widget(
-        Paragraph::new(labels_formatted)
-            .scroll((app.alternate_screen.upper_left_scroll as _, 0))
-            .block(
-                Block::default()
-                    .title("Labels")
-                    .borders(Borders::ALL)
-                    .style(label_style),
-            ),
+        Paragraph::new(labels_formatted).block(
+            Block::default()
+                .title("Labels")
+                .borders(Borders::ALL)
+                .style(label_style),
+        ),
         upper_left,
     );
     frame.render_widget(
-        Paragraph::new(volumes)
-            .scroll((app.alternate_screen.upper_right_scroll as _, 0))
-            .block(
-                Block::default()
-                    .title("Volumes")
-                    .borders(Borders::ALL)
-                    .style(volume_style),
-            ),
+        Paragraph::new(volumes).block(
+            Block::default()
+                .title("Volumes ((V) list the contents)")
+                .borders(Borders::ALL)
+                .style(volume_style),
+        ),
         upper_right,
     );
-    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-        .begin_symbol(Some("↑"))
-        .end_symbol(Some("↓"));
-    frame.render_stateful_widget(
-        scrollbar,
-        upper_right.inner(Margin {
-            vertical: 1,
-            horizontal: 0,
-        }),
+    render_scrollbar(
+        frame,
+        upper_right,
         &mut app.alternate_screen.upper_right_scroll_state,
+        app.show_scrollbars,
     );
-    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-        .begin_symbol(Some("↑"))
-        .end_symbol(Some("↓"));
-    frame.render_stateful_widget(
-        scrollbar,
-        upper_left.inner(Margin {
-            vertical: 1,
-            horizontal: 0,
-        }),
+    render_scrollbar(
+        frame,
+        upper_left,
         &mut app.alternate_screen.upper_left_scroll_state,
+        app.show_scrollbars,
+    );
+
+    let log_tail = app
+        .log_tail(selected, LOG_TAIL_LINES)
+        .into_iter()
+        .map(Line::from)
+        .collect::<Vec<_>>();
+    frame.render_widget(
+        Paragraph::new(log_tail).block(
+            Block::default()
+                .title("Latest logs")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::LightBlue).bg(bg)),
+        ),
+        header_and_main[3],
+    );
+
+    let samples = app.stats_history.samples(selected);
+    let latest = samples.last().copied().unwrap_or_default();
+    let cpu = samples
+        .iter()
+        .map(|sample| (sample.cpu_percent * 100.0) as u64)
+        .collect::<Vec<_>>();
+    let memory = samples
+        .iter()
+        .map(|sample| sample.memory)
+        .collect::<Vec<_>>();
+    let [cpu_area, memory_area, limits_area] = if app.show_limits {
+        horizontal![== 30%, == 30%, == 40%].areas(header_and_main[2])
+    } else {
+        let [cpu_area, memory_area] = horizontal![== 50%, == 50%].areas(header_and_main[2]);
+        [cpu_area, memory_area, Rect::default()]
+    };
+    frame.render_widget(
+        usage_sparkline(
+            &cpu,
+            format!("CPU {:.1}%", latest.cpu_percent),
+            Color::Yellow,
+            cpu_area,
+        ),
+        cpu_area,
     );
+    frame.render_widget(
+        usage_sparkline(
+            &memory,
+            format!("Memory {}", format_bytes(latest.memory)),
+            Color::Cyan,
+            memory_area,
+        ),
+        memory_area,
+    );
+
+    if app.show_limits {
+        let host_config = app
+            .container_info
+            .get(&selected)
+            .and_then(|info| info.as_ref())
+            .and_then(|info| info.host_config.as_ref());
+        let value_style = Style::default().fg(Color::LightYellow);
+        let mut limits = Vec::new();
+        for (name, value) in configured_limits(host_config) {
+            if !limits.is_empty() {
+                limits.push(Span::raw(", "));
+            }
+            limits.push(Span::raw(format!("{name}: ")));
+            limits.push(Span::styled(value, value_style));
+        }
+        frame.render_widget(
+            Paragraph::new(Line::from(limits)).block(
+                Block::default()
+                    .title("Configured limits")
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::LightBlue).bg(bg)),
+            ),
+            limits_area,
+        );
+    }
+
+    // Sized to the policy, but the container info keeps at least half of the header.
+    let restart_width = restart_policy_width(app).min(header_and_main[0].width / 2);
+    let [info_area, restart_area] = horizontal![>=1, ==restart_width].areas(header_and_main[0]);
+    frame.render_widget(create_restart_policy(app), restart_area);
+    frame.render_widget(create_container_info(app, info_area.width), info_area);
+}
+
+/// The recent samples of a resource, the newest on the right.
+///
+/// Only the samples fitting into the area are kept, as the sparkline drops the ones on the right.
+fn usage_sparkline(data: &[u64], title: String, color: Color, area: Rect) -> Sparkline<'_> {
+    // The borders take up 2 columns.
+    let width = area.width.saturating_sub(2) as usize;
+    Sparkline::default()
+        .data(&data[data.len().saturating_sub(width)..])
+        .style(Style::default().fg(color))
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::LightBlue).bg(get_bg_color())),
+        )
+}
+
+/// Wraps the entries starting at `offset` until they fill the area, leaving the rest untouched.
+///
+/// If `horizontal` is set, the lines are not wrapped, but shifted by that many characters instead.
+fn visible_lines(
+    entries: &[String],
+    offset: usize,
+    area: Rect,
+    horizontal: Option<usize>,
+) -> Text<'static> {
+    // The borders take up 2 rows and columns.
+    let height = area.height.saturating_sub(2) as usize;
+    let options = textwrap::Options::new(area.width.saturating_sub(2) as _);
+    let entries = entries.iter().skip(offset);
+    let lines = match horizontal {
+        Some(shift) => entries
+            .flat_map(|entry| entry.lines())
+            .take(height)
+            .map(|line| Line::from(line.chars().skip(shift).collect::<String>()))
+            .collect::<Vec<_>>(),
+        None => entries
+            .flat_map(|entry| textwrap::wrap(entry, &options))
+            .take(height)
+            .map(|s| Line::from(s.into_owned()))
+            .collect::<Vec<_>>(),
+    };
+    Text::from(lines)
+}
 
-    frame.render_widget(create_container_info(app), header_and_main[0]);
+/// The areas of the quadrants in the order of [`SplitScreen::ALL`], stacked in a single column.
+/// The focused one takes all the space, the others collapse to their title, so moving the focus
+/// scrolls through them.
+fn stacked_areas(area: Rect, focused: SplitScreen) -> [Rect; 4] {
+    let constraints = SplitScreen::ALL.map(|quadrant| {
+        if quadrant == focused {
+            Constraint::Min(1)
+        } else {
+            Constraint::Length(1)
+        }
+    });
+    let areas = Layout::vertical(constraints).split(area);
+    [areas[0], areas[1], areas[2], areas[3]]
 }
@@ -0,0 +1,55 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+use super::panel::render_panel;
+use crate::app::App;
+
+/// Every event Docker reports about the project, the newest first so it follows itself.
+pub fn render_daemon_events(app: &mut App, frame: &mut Frame) {
+    let events = app.daemon_events.events();
+    let time_style = Style::default().fg(Color::DarkGray);
+    let action_style = Style::default().fg(Color::Yellow);
+    let attribute_style = Style::default().fg(Color::Gray);
+    let tz = jiff::tz::TimeZone::system();
+
+    let mut lines = events
+        .iter()
+        .rev()
+        .map(|event| {
+            let time = event.time.to_zoned(tz.clone()).strftime("%H:%M:%S%.3f");
+            let attributes = event
+                .attributes
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Line::from(vec![
+                Span::styled(format!("{time} "), time_style),
+                Span::raw(format!("{} ", event.kind)),
+                Span::styled(format!("{} ", event.action), action_style),
+                Span::raw(format!("{} ", event.actor)),
+                Span::styled(format!("({attributes})"), attribute_style),
+            ])
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        lines.push(Line::styled(
+            "Waiting for Docker events of the project..",
+            time_style,
+        ));
+    }
+
+    render_panel(
+        app,
+        frame,
+        &format!(
+            "Docker events of {} ({}, newest first)",
+            app.project_name,
+            events.len()
+        ),
+        lines,
+    );
+}
@@ -0,0 +1,89 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr as _;
+
+use super::panel::render_panel;
+use crate::{app::App, probe::Reachability};
+
+pub fn render_dashboard(app: &mut App, frame: &mut Frame) {
+    let name_width = app
+        .compose_content
+        .compose
+        .services
+        .0
+        .keys()
+        .map(|name| name.width())
+        .max()
+        .unwrap_or_default();
+    let detail_style = Style::default().fg(Color::Gray);
+
+    let lines = app
+        .compose_content
+        .compose
+        .services
+        .0
+        .keys()
+        .enumerate()
+        .filter(|(i, _)| app.is_visible(*i))
+        .map(|(i, name)| {
+            let info = app.container_info.get(&i).and_then(|info| info.as_ref());
+            let state = info
+                .and_then(|info| info.state.as_ref()?.status)
+                .map(|status| status.to_string())
+                .unwrap_or_else(|| String::from("not running"));
+            let health = info
+                .and_then(|info| info.state.as_ref()?.health.as_ref()?.status)
+                .map(|status| status.to_string())
+                .filter(|status| !status.is_empty() && status != "none");
+            let image = info
+                .and_then(|info| info.config.as_ref()?.image.as_deref())
+                .unwrap_or_default();
+
+            let state_style = match state.as_str() {
+                "running" => Style::default().fg(Color::LightGreen),
+                "not running" => Style::default().fg(Color::Gray),
+                _ => Style::default().fg(Color::LightMagenta),
+            };
+            let padding = " ".repeat(name_width - name.width());
+            let mut spans = vec![
+                Span::styled(
+                    format!("{name}{padding}  "),
+                    Style::default().fg(Color::LightYellow),
+                ),
+                Span::styled(format!("{state:<11}"), state_style),
+            ];
+            match health.as_deref() {
+                Some("unhealthy") => {
+                    spans.push(Span::styled(
+                        format!(" {:<9}", "unhealthy"),
+                        Style::default().fg(Color::Red),
+                    ));
+                }
+                Some(health) => {
+                    spans.push(Span::styled(format!(" {health:<9}"), detail_style));
+                }
+                None => spans.push(Span::raw(" ".repeat(10))),
+            }
+            spans.push(Span::styled(format!(" {image}"), detail_style));
+            if state == "running" {
+                match app.port_probes.get(i) {
+                    Some(Reachability::Reachable) => spans.push(Span::styled(
+                        "  ports reachable",
+                        Style::default().fg(Color::Green),
+                    )),
+                    Some(Reachability::Unreachable(addresses)) => spans.push(Span::styled(
+                        format!("  unreachable: {}", addresses.join(", ")),
+                        Style::default().fg(Color::Red),
+                    )),
+                    None => {}
+                }
+            }
+            Line::from(spans)
+        })
+        .collect::<Vec<_>>();
+
+    render_panel(app, frame, "Dashboard", lines);
+}
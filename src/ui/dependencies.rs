@@ -0,0 +1,156 @@
+use docker_compose_types::DependsOnOptions;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+use super::panel::render_panel;
+use crate::app::App;
+
+/// The `depends_on` tree of the selected service with the live state of each dependency.
+pub fn render_dependencies(app: &mut App, frame: &mut Frame) {
+    let selected = app.compose_content.state.selected().unwrap_or(0);
+    let name = app
+        .compose_content
+        .compose
+        .services
+        .0
+        .get_index(selected)
+        .map(|(name, _)| name.clone())
+        .unwrap_or_default();
+
+    let mut lines = vec![service_line(app, &name, Some(selected), "", None)];
+    let mut path = vec![selected];
+    dependency_lines(app, selected, "", &mut path, &mut lines);
+    if lines.len() == 1 {
+        lines.push(Line::styled(
+            "No dependencies.",
+            Style::default().fg(Color::Gray),
+        ));
+    }
+
+    render_panel(app, frame, &format!("Dependencies of {name}"), lines);
+}
+
+/// Appends the dependencies of the service below it, indented by their depth.
+///
+/// `path` holds the services above, so dependency cycles are marked instead of recursing forever.
+fn dependency_lines(
+    app: &App,
+    idx: usize,
+    indent: &str,
+    path: &mut Vec<usize>,
+    lines: &mut Vec<Line<'static>>,
+) {
+    let services = &app.compose_content.compose.services.0;
+    let Some((_, Some(service))) = services.get_index(idx) else {
+        return;
+    };
+    let dependencies: Vec<(String, Option<String>)> = match &service.depends_on {
+        DependsOnOptions::Simple(names) => names.iter().map(|name| (name.clone(), None)).collect(),
+        DependsOnOptions::Conditional(names) => names
+            .iter()
+            .map(|(name, condition)| (name.clone(), Some(condition.condition.clone())))
+            .collect(),
+    };
+    let count = dependencies.len();
+    for (i, (name, condition)) in dependencies.into_iter().enumerate() {
+        let last = i + 1 == count;
+        let branch = if last { "└─ " } else { "├─ " };
+        let dependency = services.get_index_of(&name);
+        let prefix = format!("{indent}{branch}");
+        if dependency.is_some_and(|dependency| path.contains(&dependency)) {
+            lines.push(Line::from(vec![
+                Span::raw(prefix),
+                Span::styled(name, Style::default().fg(Color::LightYellow)),
+                Span::styled(" (cycle)", Style::default().fg(Color::Red)),
+            ]));
+            continue;
+        }
+        lines.push(service_line(
+            app,
+            &name,
+            dependency,
+            &prefix,
+            Some(condition.as_deref().unwrap_or("service_started")),
+        ));
+        if let Some(dependency) = dependency {
+            let indent = format!("{indent}{}", if last { "   " } else { "│  " });
+            path.push(dependency);
+            dependency_lines(app, dependency, &indent, path, lines);
+            path.pop();
+        }
+    }
+}
+
+/// The service with its state and health, and whether it meets the condition its dependent waits for.
+fn service_line(
+    app: &App,
+    name: &str,
+    idx: Option<usize>,
+    prefix: &str,
+    condition: Option<&str>,
+) -> Line<'static> {
+    let detail_style = Style::default().fg(Color::Gray);
+    let mut spans = vec![
+        Span::raw(prefix.to_string()),
+        Span::styled(name.to_string(), Style::default().fg(Color::LightYellow)),
+    ];
+    let Some(idx) = idx else {
+        spans.push(Span::styled(
+            " (not in the compose file)",
+            Style::default().fg(Color::Red),
+        ));
+        return Line::from(spans);
+    };
+    let state = app
+        .container_info
+        .get(&idx)
+        .and_then(|info| info.as_ref()?.state.clone());
+    let status = state
+        .as_ref()
+        .and_then(|state| state.status)
+        .map(|status| status.to_string())
+        .unwrap_or_else(|| String::from("not running"));
+    let health = state
+        .as_ref()
+        .and_then(|state| state.health.as_ref()?.status)
+        .map(|status| status.to_string())
+        .filter(|status| !status.is_empty() && status != "none");
+    let exit_code = state.as_ref().and_then(|state| state.exit_code);
+
+    let status_style = match status.as_str() {
+        "running" => Style::default().fg(Color::LightGreen),
+        "not running" => detail_style,
+        _ => Style::default().fg(Color::LightMagenta),
+    };
+    spans.push(Span::styled(format!("  {status}"), status_style));
+    if let Some(health) = &health {
+        let style = if health == "unhealthy" {
+            Style::default().fg(Color::Red)
+        } else {
+            detail_style
+        };
+        spans.push(Span::styled(format!(" {health}"), style));
+    }
+    let Some(condition) = condition else {
+        return Line::from(spans);
+    };
+    let met = match condition {
+        "service_healthy" => health.as_deref() == Some("healthy"),
+        "service_completed_successfully" => status == "exited" && exit_code == Some(0),
+        _ => status == "running",
+    };
+    spans.push(Span::styled(
+        format!("  waits for {condition}"),
+        detail_style,
+    ));
+    if !met {
+        spans.push(Span::styled(
+            " (not met)",
+            Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
+        ));
+    }
+    Line::from(spans)
+}
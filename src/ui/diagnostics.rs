@@ -0,0 +1,123 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+use super::panel::render_panel;
+use crate::app::App;
+
+/// The number of error-looking log lines shown.
+const ERROR_LOG_LINES: usize = 20;
+
+/// The phrases the Docker daemon uses when a host port is taken.
+const PORT_IN_USE: [&str; 2] = ["port is already allocated", "address already in use"];
+
+/// Collects what's known about why the selected service doesn't start into one report.
+pub fn render_diagnostics(app: &mut App, frame: &mut Frame) {
+    let selected = app.compose_content.state.selected().unwrap_or(0);
+    let service = app
+        .compose_content
+        .compose
+        .services
+        .0
+        .get_index(selected)
+        .map(|(name, _)| name.clone())
+        .unwrap_or_default();
+
+    let section_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let key_style = Style::default().fg(Color::Gray);
+    let value_style = Style::default().fg(Color::LightYellow);
+    let error_style = Style::default().fg(Color::Red);
+    let none_style = Style::default().fg(Color::DarkGray);
+    let entry = |key: &'static str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(format!("  {key}: "), key_style),
+            Span::styled(value, style),
+        ])
+    };
+
+    let mut lines = vec![Line::styled("Last command error", section_style)];
+    let error_msg = app.compose_content.error_msg.clone();
+    match &error_msg {
+        Some(error) => lines.extend(
+            error
+                .lines()
+                .map(|line| Line::styled(format!("  {line}"), error_style)),
+        ),
+        None => lines.push(Line::styled("  <none>", none_style)),
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled("Container state", section_style));
+    let state = app
+        .container_info
+        .get(&selected)
+        .and_then(|info| info.as_ref()?.state.clone());
+    match state {
+        Some(state) => {
+            let status = state
+                .status
+                .map(|status| status.to_string())
+                .unwrap_or_else(|| String::from("unknown"));
+            lines.push(entry("status", status, value_style));
+            if let Some(exit_code) = state.exit_code {
+                let style = if exit_code == 0 {
+                    value_style
+                } else {
+                    error_style
+                };
+                lines.push(entry("exit code", exit_code.to_string(), style));
+            }
+            if state.oom_killed == Some(true) {
+                lines.push(entry("OOM killed", String::from("yes"), error_style));
+            }
+            if let Some(error) = state.error.filter(|error| !error.is_empty()) {
+                lines.push(entry("error", error, error_style));
+            }
+        }
+        None => lines.push(Line::styled(
+            "  No container, it was never created or has been removed.",
+            none_style,
+        )),
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled("Recent error log lines", section_style));
+    let error_lines = app.error_log_lines(selected, ERROR_LOG_LINES);
+    if error_lines.is_empty() {
+        lines.push(Line::styled("  <none>", none_style));
+    }
+    lines.extend(
+        error_lines
+            .into_iter()
+            .map(|line| Line::styled(format!("  {line}"), value_style)),
+    );
+
+    lines.push(Line::default());
+    lines.push(Line::styled("Port conflicts", section_style));
+    let conflicts = app.port_conflicts(selected);
+    let port_in_use = error_msg.is_some_and(|error| {
+        let error = error.to_ascii_lowercase();
+        PORT_IN_USE.iter().any(|phrase| error.contains(phrase))
+    });
+    if conflicts.is_empty() && !port_in_use {
+        lines.push(Line::styled("  <none detected>", none_style));
+    }
+    for (port, other) in conflicts {
+        lines.push(Line::styled(
+            format!("  {port} is also published by {other}"),
+            error_style,
+        ));
+    }
+    if port_in_use {
+        lines.push(Line::styled(
+            "  The last command failed because a host port is already in use.",
+            error_style,
+        ));
+    }
+
+    render_panel(app, frame, &format!("Diagnostics: {service}"), lines);
+}
@@ -0,0 +1,94 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+use super::panel::render_panel;
+use crate::{
+    app::App,
+    diff::{Drift, ServiceDiff},
+};
+
+pub fn render_diff(app: &mut App, frame: &mut Frame) {
+    let title = "Compose file vs running container";
+    let selected = app.compose_content.state.selected().unwrap_or(0);
+    let service = app
+        .compose_content
+        .compose
+        .services
+        .0
+        .get_index(selected)
+        .and_then(|(_, service)| service.clone())
+        .unwrap_or_default();
+
+    let Some(Some(container_info)) = app.container_info.get(&selected) else {
+        let name = app.container_name_mapping.get(&selected).expect("to exist");
+        let line = Line::default().spans(vec![
+            Span::raw("There's nothing to compare against, "),
+            Span::styled(name.clone(), Style::default().fg(Color::Red)),
+            Span::raw(" is not running."),
+        ]);
+        render_panel(app, frame, title, vec![line]);
+        return;
+    };
+    let diff = ServiceDiff::new(&service, container_info);
+
+    let mut lines = vec![if diff.has_drift() {
+        Line::styled(
+            "The running container differs from the compose file, recreate it to apply the changes.",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Line::styled(
+            "The running container matches the compose file.",
+            Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+        )
+    }];
+    for (title, section) in [
+        ("Image", &diff.image),
+        ("Environment", &diff.env),
+        ("Ports", &diff.ports),
+        ("Volumes", &diff.volumes),
+    ] {
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            title,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+        if section.is_empty() {
+            lines.push(Line::styled("  <none>", Style::default().fg(Color::Gray)));
+        }
+        lines.extend(section.iter().map(drift_line));
+    }
+
+    render_panel(app, frame, title, lines);
+}
+
+fn drift_line(drift: &Drift) -> Line<'static> {
+    match drift {
+        Drift::Unchanged(value) => {
+            Line::styled(format!("  {value}"), Style::default().fg(Color::Gray))
+        }
+        Drift::Added(value) => Line::styled(
+            format!("+ {value} (only in container)"),
+            Style::default().fg(Color::LightGreen),
+        ),
+        Drift::Removed(value) => Line::styled(
+            format!("- {value} (only in file)"),
+            Style::default().fg(Color::Red),
+        ),
+        Drift::Changed {
+            name,
+            file,
+            runtime,
+        } => Line::styled(
+            format!("~ {name}: {file} (file) -> {runtime} (container)"),
+            Style::default().fg(Color::LightYellow),
+        ),
+    }
+}
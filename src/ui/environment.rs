@@ -0,0 +1,62 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+use super::panel::render_panel;
+use crate::{
+    app::App,
+    env::{effective_env, EnvSource},
+};
+
+pub fn render_environment(app: &mut App, frame: &mut Frame) {
+    let selected = app.compose_content.state.selected().unwrap_or(0);
+    let service = app
+        .compose_content
+        .compose
+        .services
+        .0
+        .get_index(selected)
+        .and_then(|(_, service)| service.clone())
+        .unwrap_or_default();
+
+    let entries = effective_env(&service, &app.dotenv);
+    let mut lines = Vec::with_capacity(entries.len());
+    if entries.is_empty() {
+        lines.push(Line::styled(
+            "The service doesn't declare any environment variables.",
+            Style::default().fg(Color::Gray),
+        ));
+    }
+    for entry in entries {
+        let source_style = if entry
+            .sources
+            .iter()
+            .any(|s| matches!(s, EnvSource::Unset(_)))
+        {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        let sources = entry
+            .sources
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(Line::from(vec![
+            Span::styled(
+                entry.name,
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("="),
+            Span::styled(entry.value, Style::default().fg(Color::LightYellow)),
+            Span::styled(format!("  ({sources})"), source_style),
+        ]));
+    }
+
+    render_panel(app, frame, "Effective environment", lines);
+}
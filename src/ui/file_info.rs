@@ -0,0 +1,129 @@
+use docker_compose_types::{DependsOnOptions, Service};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+use super::panel::render_panel;
+use crate::app::App;
+
+/// The format of the compose file and the compose features its services use.
+pub fn render_file_info(app: &mut App, frame: &mut Frame) {
+    let compose = &app.compose_content.compose;
+    let services = compose
+        .services
+        .0
+        .iter()
+        .map(|(name, service)| (name.as_str(), service.clone().unwrap_or_default()))
+        .collect::<Vec<_>>();
+    let using = |uses: fn(&Service) -> bool| {
+        services
+            .iter()
+            .filter(|(_, service)| uses(service))
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+    };
+
+    let section_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let value_style = Style::default().fg(Color::LightYellow);
+    let unused_style = Style::default().fg(Color::DarkGray);
+
+    let format = match compose.version.as_deref().map(str::trim) {
+        Some(version) if version.starts_with(['1', '2']) => {
+            format!("version {version} (legacy format)")
+        }
+        Some(version) => format!("version {version} (ignored by the Compose Specification)"),
+        None => String::from("Compose Specification (no version)"),
+    };
+    let mut lines = vec![
+        Line::styled("Format", section_style),
+        Line::from(vec![Span::raw("  "), Span::styled(format, value_style)]),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!(
+                    "{} service(s), {} network(s), {} volume(s)",
+                    services.len(),
+                    compose.networks.0.len(),
+                    compose.volumes.0.len()
+                ),
+                value_style,
+            ),
+        ]),
+        Line::default(),
+        Line::styled("Features", section_style),
+    ];
+
+    let mut profiles = services
+        .iter()
+        .flat_map(|(_, service)| service.profiles.iter().cloned())
+        .collect::<Vec<_>>();
+    profiles.sort();
+    profiles.dedup();
+    let top_level_secrets = compose
+        .secrets
+        .as_ref()
+        .map_or(0, |secrets| secrets.0.len());
+    let extensions = compose
+        .extensions
+        .keys()
+        // The key has no accessor, but it serializes as the plain string.
+        .filter_map(|key| serde_json::to_value(key).ok()?.as_str().map(String::from))
+        .collect::<Vec<_>>();
+    let features = [
+        (
+            "profiles",
+            using(|service| !service.profiles.is_empty()),
+            (!profiles.is_empty()).then(|| format!("profiles {}", profiles.join(", "))),
+        ),
+        (
+            "healthchecks",
+            using(|service| service.healthcheck.is_some()),
+            None,
+        ),
+        ("build", using(|service| service.build_.is_some()), None),
+        ("deploy", using(|service| service.deploy.is_some()), None),
+        (
+            "secrets",
+            using(|service| service.secrets.as_ref().is_some_and(|s| !s.is_empty())),
+            (top_level_secrets > 0).then(|| format!("{top_level_secrets} declared")),
+        ),
+        (
+            "extends",
+            using(|service| !service.extends.is_empty()),
+            None,
+        ),
+        (
+            "depends_on",
+            using(|service| match &service.depends_on {
+                DependsOnOptions::Simple(deps) => !deps.is_empty(),
+                DependsOnOptions::Conditional(deps) => !deps.is_empty(),
+            }),
+            None,
+        ),
+        (
+            "extensions",
+            Vec::new(),
+            (!extensions.is_empty()).then(|| extensions.join(", ")),
+        ),
+    ];
+    for (feature, services, detail) in features {
+        let mut line = Line::from(Span::raw(format!("  {feature:<14}")));
+        if services.is_empty() && detail.is_none() {
+            line.push_span(Span::styled("not used", unused_style));
+        } else {
+            let mut parts = Vec::new();
+            if !services.is_empty() {
+                parts.push(services.join(", "));
+            }
+            parts.extend(detail);
+            line.push_span(Span::styled(parts.join("; "), value_style));
+        }
+        lines.push(line);
+    }
+
+    render_panel(app, frame, "File info", lines);
+}
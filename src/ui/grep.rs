@@ -0,0 +1,87 @@
+use indexmap::IndexMap;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr as _;
+
+use super::panel::render_panel;
+use crate::{
+    app::{App, MAX_GREP_MATCHES},
+    utils::truncate_to_width,
+};
+
+/// The rows above the results, the match counts and an empty line.
+const HEADER_ROWS: usize = 2;
+
+/// The log lines of every service that contain the searched text, with the match counts per service.
+pub fn render_grep(app: &mut App, frame: &mut Frame) {
+    let services = &app.compose_content.compose.services.0;
+    let name = |idx: usize| {
+        services
+            .get_index(idx)
+            .map(|(name, _)| name.as_str())
+            .unwrap_or_default()
+    };
+    let mut counts = IndexMap::<usize, usize>::new();
+    for found in &app.grep_matches {
+        *counts.entry(found.service).or_default() += 1;
+    }
+    let name_width = counts
+        .keys()
+        .map(|idx| name(*idx).width())
+        .max()
+        .unwrap_or_default();
+
+    let header_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut summary = counts
+        .iter()
+        .map(|(idx, count)| format!("{} {count}", name(*idx)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if summary.is_empty() {
+        summary = String::from("No matches");
+    }
+    if app.grep_matches.len() == MAX_GREP_MATCHES {
+        summary.push_str(&format!(" (stopped at {MAX_GREP_MATCHES} matches)"));
+    }
+    let mut lines = vec![Line::styled(summary, header_style), Line::default()];
+
+    // The results are cut to the width, so each takes up a single row and the selection can be kept in view.
+    let text_width = (frame.area().width.saturating_sub(2) as usize).saturating_sub(name_width + 3);
+    for (i, found) in app.grep_matches.iter().enumerate() {
+        let service = name(found.service);
+        let padding = " ".repeat(name_width - service.width());
+        let mut line = Line::from(vec![
+            Span::styled(
+                format!("[{service}]{padding} "),
+                Style::default().fg(Color::LightYellow),
+            ),
+            Span::raw(truncate_to_width(&found.text, text_width)),
+        ]);
+        if i == app.grep_selected {
+            line = line.style(Style::default().add_modifier(Modifier::REVERSED));
+        }
+        lines.push(line);
+    }
+
+    // The header and the panel borders take up 5 rows.
+    let height = frame.area().height.saturating_sub(5) as usize;
+    let selected_row = HEADER_ROWS + app.grep_selected;
+    let scroll = &mut app.alternate_screen.panel_scroll;
+    if selected_row < *scroll {
+        *scroll = selected_row.saturating_sub(HEADER_ROWS);
+    } else if height > 0 && selected_row >= *scroll + height {
+        *scroll = selected_row + 1 - height;
+    }
+    app.alternate_screen.panel_scroll_state = app
+        .alternate_screen
+        .panel_scroll_state
+        .position(app.alternate_screen.panel_scroll);
+
+    let title = format!("Search \"{}\" in every service", app.grep_query);
+    render_panel(app, frame, &title, lines);
+}
@@ -12,7 +12,8 @@ use super::get_bg_color;
 
 pub fn render_help(frame: &mut Frame) {
     let bg = get_bg_color();
-    let [_, inner_area, _] = vertical![>=0, <=7, >=0].areas(frame.area());
+    // The borders take up 2 columns.
+    let width = frame.area().width.saturating_sub(2) as usize;
     frame.render_widget(
         Block::default()
             .title("Help")
@@ -48,6 +49,13 @@ pub fn render_help(frame: &mut Frame) {
                 .fg(Color::Magenta),
         ),
         Span::raw(" stop selected, "),
+        Span::styled(
+            "(S) / (T)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" stop / start selected keeping the container, "),
         Span::styled(
             "(x)",
             Style::default()
@@ -61,7 +69,28 @@ pub fn render_help(frame: &mut Frame) {
                 .add_modifier(Modifier::BOLD)
                 .fg(Color::Magenta),
         ),
-        Span::raw(" restart selected"),
+        Span::raw(" restart selected, "),
+        Span::styled(
+            "(u)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" pull and recreate selected, "),
+        Span::styled(
+            "(R)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" reset selected (stop, remove and start again), "),
+        Span::styled(
+            "(C)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" copy files to / from selected"),
     ]);
 
     let navigation = Line::default().spans(vec![
@@ -92,6 +121,41 @@ pub fn render_help(frame: &mut Frame) {
                 .fg(Color::Magenta),
         ),
         Span::raw("navigate container list (jump to first / last), "),
+        Span::styled(
+            "(i) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("jump to first unhealthy/running service, "),
+        Span::styled(
+            "(b) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("jump logs to the last container exit, "),
+        Span::styled(
+            "(o) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("hide/show stopped services, "),
+        Span::styled(
+            "(V) (space) (+) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("group services by profile/dependency/label, collapse group, expand all, "),
+        Span::styled(
+            "(F) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("filter services by labels, "),
         Span::styled(
             "(e) ",
             Style::default()
@@ -99,13 +163,162 @@ pub fn render_help(frame: &mut Frame) {
                 .fg(Color::Magenta),
         ),
         Span::raw("enter alternate screen, "),
+        Span::styled(
+            "(d) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("compare compose file with running container, "),
+        Span::styled(
+            "(v) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("show effective environment, "),
+        Span::styled(
+            "(g) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("show top-level networks and volumes, "),
+        Span::styled(
+            "(ctrl + p) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("show docker compose ps, "),
+        Span::styled(
+            "(D) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("show dashboard, "),
+        Span::styled(
+            "(B) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("show build context and dockerfile, "),
+        Span::styled(
+            "(W) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("diagnose why the service won't start, "),
+        Span::styled(
+            "(N) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("compare the guessed and actual container names, "),
+        Span::styled(
+            "(J) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("show the activity of the project, (tab) filters events / logs, "),
+        Span::styled(
+            "(M) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("maximize logs, "),
+        Span::styled(
+            "(A) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("show the logs of all services / the selected one, "),
+        Span::styled(
+            "([) (]) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("fetch less/more log history, "),
+        Span::styled(
+            "(H) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("freeze the logs and mark what arrives after, "),
+        Span::styled(
+            "(ctrl + n) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("show only the logs since the mark, "),
+        Span::styled(
+            "(U) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("show when the containers started in the list, "),
+        Span::styled(
+            "(X) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("show the configured resource limits in the details, "),
+        Span::styled(
+            "(K) ",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw("collapse the multi-line log entries, "),
         Span::styled(
             "(tab)",
             Style::default()
                 .add_modifier(Modifier::BOLD)
                 .fg(Color::Magenta),
         ),
-        Span::raw(" move focus on alternate screen"),
+        Span::raw(" move focus on alternate screen, "),
+        Span::styled(
+            "(ctrl + o)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" switch the focus order in the details between columns and rows, "),
+        Span::styled(
+            "(w) ← / →",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" toggle wrapping / scroll horizontally on alternate screen, "),
+        Span::styled(
+            "(G) (space)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(
+            " group labels by namespace / collapse the group at the top on alternate screen, ",
+        ),
+        Span::styled(
+            "(p) / (P)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" switch to next / previous project"),
     ]);
 
     let bottom_line = Line::default().spans(vec![
@@ -129,6 +342,174 @@ pub fn render_help(frame: &mut Frame) {
                 .fg(Color::Magenta),
         ),
         Span::raw(" clear logs, "),
+        Span::styled(
+            "(ctrl + k)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" interrupt running commands, "),
+        Span::styled(
+            "(ctrl + g)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" repaint the screen, "),
+        Span::styled(
+            "(ctrl + b)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" show / hide the scrollbars, "),
+        Span::styled(
+            "(ctrl + a) (G)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" bookmark the service / focus the next bookmarked one, "),
+        Span::styled(
+            "(ctrl + d)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" follow the raw Docker events of the project, "),
+        Span::styled(
+            "(ctrl + y)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" show the file format and the features used, "),
+        Span::styled(
+            "(ctrl + f)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" filter the services by name, Esc restores the list, "),
+        Span::styled(
+            "(ctrl + x)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" show the dependencies of the service with their state, "),
+        Span::styled(
+            "(ctrl + t)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" pick the service for the split logs, (tab) switches the panes, "),
+        Span::styled(
+            "(ctrl + u)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" unsplit the logs, "),
+        Span::styled(
+            "(ctrl + e)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" wrap logs at words / characters, "),
+        Span::styled(
+            "(l)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" show logs since a relative time, "),
+        Span::styled(
+            "(L)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" restart the log stream, "),
+        Span::styled(
+            "(#)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" toggle line numbers, "),
+        Span::styled(
+            "(/)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" search the logs, (n) / (N) jump to the next / previous match, "),
+        Span::styled(
+            "(?)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" search the logs of every service, (enter) jumps to the selected line, "),
+        Span::styled(
+            "(end)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" follow the new log lines, scrolling up stops following, "),
+        Span::styled(
+            "(t)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" toggle timestamps, this clears the logs and streams them again, "),
+        Span::styled(
+            "(m)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" toggle quiet mode without error popups, "),
+        Span::styled(
+            "(y) / (Y)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" copy logs / as markdown code block, "),
+        Span::styled(
+            "(c)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" copy the command of starting all containers, "),
+        Span::styled(
+            "(I)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" copy a session report for bug reports, "),
+        Span::styled(
+            "(z) / (Z)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" export logs of selected / all services to a file, "),
+        Span::styled(
+            "(ctrl + r)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" retry the last failed command, "),
         Span::styled(
             "(ctrl + w)",
             Style::default()
@@ -142,32 +523,36 @@ pub fn render_help(frame: &mut Frame) {
                 .add_modifier(Modifier::BOLD)
                 .fg(Color::Magenta),
         ),
-        Span::raw(" remove all containers with volumes, "),
+        Span::raw(" remove all containers with volumes (these and (x) ask for the project name with --safe), "),
         Span::styled(
             "(q)",
             Style::default()
                 .add_modifier(Modifier::BOLD)
                 .fg(Color::Magenta),
         ),
-        Span::raw(" to quit."),
+        Span::raw(" to quit, "),
+        Span::styled(
+            "(Q)",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Magenta),
+        ),
+        Span::raw(" to quit and run docker compose down."),
     ]);
 
-    let mut text = wrap_line(
-        &text,
-        Options::from_width_and_header(inner_area.width as _, "Basic"),
-    );
+    let mut text = wrap_line(&text, Options::from_width_and_header(width, "Basic"));
     let navigation = wrap_line(
         &navigation,
-        Options::from_width_and_header(inner_area.width as _, "Navigation"),
-    );
-    let bottom_line = wrap_line(
-        &bottom_line,
-        Options::from_width_and_header(inner_area.width as _, "Meta"),
+        Options::from_width_and_header(width, "Navigation"),
     );
+    let bottom_line = wrap_line(&bottom_line, Options::from_width_and_header(width, "Meta"));
 
     text.lines.extend(navigation.lines);
     text.lines.extend(bottom_line.lines);
 
+    let height = text.lines.len() as u16 + 2;
+    let [_, inner_area, _] = vertical![>=0, ==height, >=0].areas(frame.area());
+
     frame.render_widget(
         Paragraph::new(text).block(
             Block::default()
@@ -1,60 +1,164 @@
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
 
+use unicode_width::UnicodeWidthStr as _;
+
 use crate::{
     app::{App, DockerModifier},
-    utils::shorten_path,
+    stats::format_bytes,
+    utils::{fit_widths, shorten_path, truncate_to_width},
 };
 
 use super::get_bg_color;
 
-pub fn create_legend(app: &App) -> Paragraph<'_> {
+/// The frames of the activity indicator.
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// The general project info, with the project name and the path truncated to fit into `width` columns.
+pub fn create_legend(app: &App, width: u16) -> Paragraph<'_> {
     let bg = get_bg_color();
-    let content = Line::from(vec![
-        Span::raw("Project name: "),
-        Span::styled(
-            app.project_name.as_str(),
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Magenta),
-        ),
-        Span::raw(" File: "),
+    let value_style = Style::default()
+        .add_modifier(Modifier::BOLD)
+        .fg(Color::Magenta);
+    let position = if app.projects_len() > 1 {
+        format!(" ({}/{})", app.active_project + 1, app.projects_len())
+    } else {
+        String::new()
+    };
+    let path = shorten_path(app.full_path.as_path())
+        .to_string_lossy()
+        .into_owned();
+    let starting = app.compose_content.start_queued.state.len();
+    let stopping = app.compose_content.stop_queued.state.len();
+    let queued = starting > 0 || stopping > 0;
+    let (starting, stopping) = (format!("↑{starting}"), format!("↓{stopping}"));
+
+    // The borders take up 2 columns.
+    let available = width.saturating_sub(2) as usize;
+    let labels = [
+        "Project name: ",
+        " File: ",
+        " Docker version: ",
+        " Queued: ",
+    ];
+    let mut fixed = labels[..3].iter().map(|label| label.width()).sum::<usize>()
+        + position.width()
+        + app.docker_version.width();
+    if queued {
+        fixed += labels[3].width() + starting.width() + 1 + stopping.width();
+    }
+    // The docker version and the queue are short, so only the name and the path give way.
+    let budgets = fit_widths(
+        &[app.project_name.width(), path.width()],
+        available.saturating_sub(fixed),
+    );
+
+    let mut content = Line::from(vec![
+        Span::raw(labels[0]),
         Span::styled(
-            shorten_path(app.full_path.as_path())
-                .to_string_lossy()
-                .into_owned(),
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Magenta),
+            truncate_to_width(&app.project_name, budgets[0]),
+            value_style,
         ),
-        Span::raw(" Docker version: "),
-        Span::styled(
-            &app.docker_version,
+        Span::raw(position),
+        Span::raw(labels[1]),
+        Span::styled(truncate_to_width(&path, budgets[1]), value_style),
+        Span::raw(labels[2]),
+        Span::styled(&app.docker_version, value_style),
+    ]);
+    if queued {
+        content.push_span(Span::raw(labels[3]));
+        content.push_span(Span::styled(
+            starting,
             Style::default()
                 .add_modifier(Modifier::BOLD)
-                .fg(Color::Magenta),
-        ),
-    ]);
+                .fg(Color::Yellow),
+        ));
+        content.push_span(Span::raw(" "));
+        content.push_span(Span::styled(
+            stopping,
+            Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
+        ));
+    }
 
-    Paragraph::new(content).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title("General")
-            .style(Style::default().fg(Color::LightBlue).bg(bg)),
-    )
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title("General")
+        .style(Style::default().fg(Color::LightBlue).bg(bg));
+    if app.in_flight > 0 {
+        let spinner = SPINNER[app.ticks % SPINNER.len()];
+        block = block.title(
+            Line::styled(
+                format!("{spinner} {} running", app.in_flight),
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .fg(Color::Yellow),
+            )
+            .right_aligned(),
+        );
+    }
+
+    if let Some(status) = app.status() {
+        block = block.title(
+            Line::styled(
+                status,
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .fg(Color::Green),
+            )
+            .right_aligned(),
+        );
+    }
+
+    if app.quiet {
+        block = block.title(
+            Line::styled(
+                format!("quiet, {} suppressed error(s)", app.suppressed_errors),
+                Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
+            )
+            .right_aligned(),
+        );
+    }
+
+    Paragraph::new(content).block(block)
+}
+
+/// The number of rows the modifier toggles need in `width` columns, they wrap to a second row if they don't fit.
+pub fn docker_modifiers_height(modifiers: DockerModifier, width: u16) -> u16 {
+    // The borders take up 2 columns and 2 rows.
+    if modifiers_line(modifiers).width() > width.saturating_sub(2) as usize {
+        4
+    } else {
+        3
+    }
 }
 
-pub fn create_docker_modifiers(modifiers: DockerModifier) -> Paragraph<'static> {
+/// The modifier toggles, with the command line that starting all services would run at the bottom.
+pub fn create_docker_modifiers(modifiers: DockerModifier, command: String) -> Paragraph<'static> {
     let bg = get_bg_color();
+    Paragraph::new(modifiers_line(modifiers))
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .title("Docker Modifiers")
+                .title_bottom(Line::styled(
+                    format!("(c) copy: {command}"),
+                    Style::default().fg(Color::DarkGray),
+                ))
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::LightBlue).bg(bg)),
+        )
+}
+
+fn modifiers_line(modifiers: DockerModifier) -> Line<'static> {
     let style_on = Style::default()
         .add_modifier(Modifier::BOLD)
         .fg(Color::Green);
 
     let style_off = Style::default().fg(Color::Red);
-    let text = Line::default().spans(vec![
+    Line::default().spans(vec![
         Span::raw("(1) Build: "),
         Span::styled(
             if modifiers.contains(DockerModifier::BUILD) {
@@ -120,17 +224,37 @@ pub fn create_docker_modifiers(modifiers: DockerModifier) -> Paragraph<'static>
                 style_off
             },
         ),
-    ]);
-
-    Paragraph::new(text).block(
-        Block::default()
-            .title("Docker Modifiers")
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::LightBlue).bg(bg)),
-    )
+        Span::raw(", (6) Remove orphans: "),
+        Span::styled(
+            if modifiers.contains(DockerModifier::REMOVE_ORPHANS) {
+                "ON"
+            } else {
+                "OFF"
+            },
+            if modifiers.contains(DockerModifier::REMOVE_ORPHANS) {
+                style_on
+            } else {
+                style_off
+            },
+        ),
+        Span::raw(", (7) Renew anonymous volumes: "),
+        Span::styled(
+            if modifiers.contains(DockerModifier::RENEW_ANON_VOLUMES) {
+                "ON"
+            } else {
+                "OFF"
+            },
+            if modifiers.contains(DockerModifier::RENEW_ANON_VOLUMES) {
+                style_on
+            } else {
+                style_off
+            },
+        ),
+    ])
 }
 
-pub fn create_container_info(app: &mut App) -> impl Widget + '_ {
+/// The summary of the selected container, truncated to fit into `width` columns.
+pub fn create_container_info(app: &mut App, width: u16) -> impl Widget + '_ {
     let bg = get_bg_color();
     // A bit ugly to duplicate, but it's only 2 blocks..
     let Some(selected) = app.compose_content.state.selected() else {
@@ -158,6 +282,8 @@ pub fn create_container_info(app: &mut App) -> impl Widget + '_ {
         );
     };
     let value_style = Style::default().fg(Color::LightYellow);
+    // Inspecting may fail for a moment during restarts, the last known info is shown meanwhile.
+    let stale = app.info_stale_for(selected);
 
     let name = container_info.name.as_deref().unwrap_or_default();
     let created = container_info.created.as_deref().unwrap_or_default();
@@ -178,22 +304,114 @@ pub fn create_container_info(app: &mut App) -> impl Widget + '_ {
         .and_then(|state| state.status.map(|status| status.to_string()))
         .unwrap_or_else(|| String::from("unknown"));
 
+    // The numbers of a stopped container would be stale, so they're only shown while it's running.
+    let running = app
+        .container_name_mapping
+        .get(&selected)
+        .is_some_and(|name| app.running_container_names.contains(name));
+    let usage = app.container_stats.lock().unwrap().get(&selected).copied();
+    let (cpu, memory) = match usage {
+        Some((cpu, memory)) if running => (format!("{cpu:.1}%"), format_bytes(memory)),
+        _ => (String::from("-"), String::from("-")),
+    };
+
+    // The borders take up 2 columns.
+    let available = width.saturating_sub(2) as usize;
+    let num_of_volumes = num_of_volumes.to_string();
+    let labels = [
+        "image: ",
+        " name: ",
+        " created: ",
+        " state: ",
+        " attached volumes: ",
+        " cpu: ",
+        " memory: ",
+    ];
+    let fixed = labels.iter().map(|label| label.width()).sum::<usize>()
+        + state.width()
+        + num_of_volumes.width()
+        + cpu.width()
+        + memory.width();
+    // The state, the volume count and the usage are short and the most important, so only the rest is truncated.
+    let truncated = [image, name, created];
+    let budgets = fit_widths(
+        &truncated.map(|value| value.width()),
+        available.saturating_sub(fixed),
+    );
+    let [image, name, created] =
+        std::array::from_fn(|i| truncate_to_width(truncated[i], budgets[i]));
+
     let content = Line::from(vec![
-        Span::raw("image: "),
+        Span::raw(labels[0]),
         Span::styled(image, value_style),
-        Span::raw(" name: "),
+        Span::raw(labels[1]),
         Span::styled(name, value_style),
-        Span::raw(" created: "),
+        Span::raw(labels[2]),
         Span::styled(created, value_style),
-        Span::raw(" state: "),
+        Span::raw(labels[3]),
         Span::styled(state, value_style),
-        Span::raw(" attached volumes: "),
-        Span::styled(num_of_volumes.to_string(), value_style),
+        Span::raw(labels[4]),
+        Span::styled(num_of_volumes, value_style),
+        Span::raw(labels[5]),
+        Span::styled(cpu, value_style),
+        Span::raw(labels[6]),
+        Span::styled(memory, value_style),
     ]);
-    Paragraph::new(content).block(
+    let mut block = Block::default()
+        .title("Container info")
+        .borders(Borders::ALL)
+        .style(Style::default().fg(Color::LightBlue).bg(bg));
+    if let Some(stale) = stale {
+        block = block.title(Line::styled(
+            format!("(stale {}s)", stale.as_secs()),
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        ));
+    }
+    Paragraph::new(content).block(block)
+}
+
+/// The number of columns the restart policy needs, with the borders.
+pub fn restart_policy_width(app: &App) -> u16 {
+    let title = "Restart policy".width();
+    (restart_policy_line(app).width().max(title) + 2) as u16
+}
+
+pub fn create_restart_policy(app: &App) -> Paragraph<'_> {
+    let bg = get_bg_color();
+    Paragraph::new(restart_policy_line(app)).block(
         Block::default()
-            .title("Container info")
+            .title("Restart policy")
             .borders(Borders::ALL)
             .style(Style::default().fg(Color::LightBlue).bg(bg)),
     )
 }
+
+fn restart_policy_line(app: &App) -> Line<'_> {
+    let value_style = Style::default().fg(Color::LightYellow);
+    let selected = app.compose_content.state.selected().unwrap_or(0);
+
+    let declared = app.declared_restart_policy(selected);
+    let runtime = app.runtime_restart_policy(selected);
+
+    let mut content = vec![
+        Span::raw("file: "),
+        Span::styled(declared.unwrap_or("default (no)"), value_style),
+        Span::raw(" runtime: "),
+    ];
+    match runtime {
+        Some(runtime) => {
+            let stale = runtime != declared.unwrap_or("no");
+            content.push(Span::styled(runtime, value_style));
+            if stale {
+                content.push(Span::styled(
+                    " (differs, recreate to apply)",
+                    Style::default().add_modifier(Modifier::BOLD).fg(Color::Red),
+                ));
+            }
+        }
+        None => content.push(Span::styled("not running", Style::default().fg(Color::Red))),
+    }
+    Line::from(content)
+}
@@ -1,22 +1,34 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Margin, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
-    text::{Line, Text},
+    text::{Line, Span, Text},
     widgets::{
-        Block, BorderType, List, ListDirection, ListItem, Paragraph, Scrollbar,
-        ScrollbarOrientation,
+        Block, BorderType, List, ListDirection, ListItem, ListState, Paragraph, ScrollbarState,
     },
     Frame,
 };
+use ratatui_macros::vertical;
+use textwrap::{core::Word, WordSeparator, WrapAlgorithm};
+use unicode_width::UnicodeWidthStr as _;
 
-use crate::app::App;
+use crate::{
+    app::{App, HighlightStyle},
+    probe::Reachability,
+    utils::{find_matches, format_relative, split_timestamp},
+};
 
 use super::{
     get_bg_color,
-    legend::{create_container_info, create_docker_modifiers, create_legend},
+    legend::{
+        create_container_info, create_docker_modifiers, create_legend, docker_modifiers_height,
+    },
     popup::Popup,
+    render_scrollbar,
 };
 
+/// The width of the uptime column, fits "59m ago".
+const UPTIME_WIDTH: usize = 7;
+
 pub fn render_main_screen(app: &mut App, frame: &mut Frame) {
     let bg = get_bg_color();
     let size = frame.area();
@@ -25,9 +37,10 @@ pub fn render_main_screen(app: &mut App, frame: &mut Frame) {
         .constraints([Constraint::Min(1), Constraint::Length(3)])
         .split(size);
 
+    let modifiers_height = docker_modifiers_height(app.compose_content.modifiers, size.width);
     let main_and_modifier = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(1), Constraint::Length(3)])
+        .constraints([Constraint::Min(1), Constraint::Length(modifiers_height)])
         .split(main_and_legend[0]);
 
     let main_and_logs = Layout::default()
@@ -39,103 +52,170 @@ pub fn render_main_screen(app: &mut App, frame: &mut Frame) {
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(1), Constraint::Length(3)])
         .split(main_and_logs[1]);
-    frame.render_widget(create_container_info(app), logs_and_info[1]);
-
-    let content = app
-        .compose_content
-        .logs
-        .lock()
-        .unwrap()
-        .get(&app.compose_content.state.selected().unwrap_or(0))
-        .cloned()
-        .unwrap_or_default();
-    app.vertical_scroll_state = app
-        .vertical_scroll_state
-        .viewport_content_length(20)
-        .content_length(content.len());
-    let wrapped = Text::from(
-        textwrap::wrap(
-            &content.join(""),
-            // Terminating 3 pixels before is a bit nicer
-            textwrap::Options::new(logs_and_info[0].width.saturating_sub(3) as _),
-        )
-        .iter()
-        .map(|s| Line::from(s.to_string()))
-        .collect::<Vec<_>>(),
-    );
     frame.render_widget(
-        Paragraph::new(wrapped)
-            .block(
-                Block::bordered()
-                    .title("Logs")
-                    .border_type(BorderType::Rounded)
-                    .style(Style::default().fg(Color::LightBlue).bg(bg)),
-            )
-            .scroll((app.vertical_scroll as _, 0)),
-        logs_and_info[0],
+        create_container_info(app, logs_and_info[1].width),
+        logs_and_info[1],
     );
 
-    let items: Vec<ListItem> = app
+    render_logs(app, frame, logs_and_info[0]);
+    let selected = app.compose_content.state.selected().unwrap_or(0);
+
+    let visible = app.visible_services();
+    // Collapsed groups still show their header, so only the filtered services count as hidden.
+    let hidden = (0..app.services_len)
+        .filter(|i| !app.passes_filters(*i))
+        .count();
+    let mut title = String::from("Docker Compose TUI");
+    if let Some(selector) = &app.label_selector {
+        title.push_str(&format!(" [{selector}]"));
+    }
+    if let Some(filter) = &app.service_filter {
+        title.push_str(&format!(" ~{filter}"));
+    }
+    if hidden > 0 {
+        title.push_str(&format!(" ({hidden} hidden)"));
+    }
+    // With the uptime column, the names are padded so the times line up.
+    let indent_width = if app.service_groups().is_some() { 2 } else { 0 };
+    let name_width = app
         .compose_content
         .compose
         .services
         .0
         .keys()
-        .enumerate()
-        .zip(app.container_name_mapping.values())
-        .map(|((i, display_name), real_name)| {
-            let content = Text::raw(display_name);
-            let style = if app.compose_content.start_queued.state.contains(&i) {
-                Style::default().fg(Color::Yellow)
-            } else if app.compose_content.stop_queued.state.contains(&i) {
-                Style::default().fg(Color::Red)
-            } else if app.running_container_names.iter().any(|m| m == real_name) {
-                Style::default().fg(Color::LightGreen)
-            } else {
-                Style::default().fg(Color::Gray)
-            };
-            ListItem::new(content).style(style)
-        })
-        .collect();
+        .map(|name| name.width())
+        .max()
+        .unwrap_or_default()
+        + indent_width;
+    let now = jiff::Timestamp::now();
+    let service_item = |i: usize, indent: &str| {
+        let display_name = app
+            .compose_content
+            .compose
+            .services
+            .0
+            .get_index(i)
+            .map(|(name, _)| name.as_str())
+            .unwrap_or_default();
+        let real_name = app.container_name_mapping.get(&i);
+        let is_named = |names: &[String]| real_name.is_some_and(|name| names.contains(name));
+        let mut content = if app.show_uptime {
+            let started = app
+                .container_info
+                .get(&i)
+                .and_then(|info| info.as_ref()?.state.as_ref()?.started_at.as_deref())
+                .and_then(|started| started.parse::<jiff::Timestamp>().ok())
+                .filter(|_| is_named(&app.running_container_names))
+                .map(|started| format_relative(started, now))
+                .unwrap_or_else(|| String::from("-"));
+            Line::from(vec![
+                Span::raw(format!("{:name_width$}", format!("{indent}{display_name}"))),
+                Span::styled(
+                    format!(" {started:>UPTIME_WIDTH$}"),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        } else {
+            Line::raw(format!("{indent}{display_name}"))
+        };
+        // Only running services are probed, the results of the stopped ones are stale.
+        if is_named(&app.running_container_names) {
+            match app.port_probes.get(i) {
+                Some(Reachability::Reachable) => {
+                    content.push_span(Span::styled(" ●", Style::default().fg(Color::Green)));
+                }
+                Some(Reachability::Unreachable(_)) => {
+                    content.push_span(Span::styled(" ●", Style::default().fg(Color::Red)));
+                }
+                None => {}
+            }
+        }
+        if app.is_bookmarked(i) {
+            content.push_span(Span::styled(" ★", Style::default().fg(Color::Yellow)));
+        }
+        if app.split_panes.iter().any(|pane| pane.service == i) {
+            content.push_span(Span::styled(" ◧", Style::default().fg(Color::Cyan)));
+        }
+        let style = if app.compose_content.start_queued.state.contains(&i) {
+            Style::default().fg(Color::Yellow)
+        } else if app.compose_content.stop_queued.state.contains(&i) {
+            Style::default().fg(Color::Red)
+        } else if is_named(&app.running_container_names) {
+            Style::default().fg(Color::LightGreen)
+        } else if is_named(&app.stopped_container_names) {
+            // Stopped, but the container is kept, so it can be started again.
+            Style::default().fg(Color::LightMagenta)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        ListItem::new(content).style(style)
+    };
 
+    // The rows of the list, with the service index for the rows that aren't group headers.
+    let mut rows: Vec<(Option<usize>, ListItem)> = Vec::new();
+    match app.service_groups() {
+        Some(groups) => {
+            for (name, services) in groups {
+                let collapsed = app.collapsed_groups.contains(&name);
+                let marker = if collapsed { '▶' } else { '▼' };
+                rows.push((
+                    None,
+                    ListItem::new(format!("{marker} {name} ({})", services.len())).style(
+                        Style::default()
+                            .fg(Color::LightBlue)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ));
+                if !collapsed {
+                    rows.extend(
+                        services
+                            .into_iter()
+                            .map(|i| (Some(i), service_item(i, "  "))),
+                    );
+                }
+            }
+        }
+        None => rows.extend(visible.iter().map(|i| (Some(*i), service_item(*i, "")))),
+    }
+    // The list only knows about its rows, so the selection is mapped to the position of the selected service.
+    let selected_row = rows.iter().position(|(i, _)| *i == Some(selected));
+    let items = rows.into_iter().map(|(_, item)| item).collect::<Vec<_>>();
+
+    let highlight_style = match app.highlight_style {
+        HighlightStyle::Emphasis => Style::default()
+            .add_modifier(Modifier::ITALIC)
+            .add_modifier(Modifier::BOLD),
+        HighlightStyle::Reverse => Style::default().add_modifier(Modifier::REVERSED),
+        HighlightStyle::Underline => Style::default().add_modifier(Modifier::UNDERLINED),
+    };
     let list = List::new(items)
         .style(Style::default().fg(Color::White))
-        .highlight_style(
-            Style::default()
-                .add_modifier(Modifier::ITALIC)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol(">>")
+        .highlight_style(highlight_style)
+        .highlight_symbol(&app.highlight_symbol)
         .repeat_highlight_symbol(true)
         .direction(ListDirection::TopToBottom)
         .block(
             Block::bordered()
-                .title("Docker Compose TUI")
+                .title(title)
                 .border_type(BorderType::Rounded)
                 .style(Style::default().fg(Color::LightBlue).bg(bg)),
         );
 
-    frame.render_stateful_widget(list, main_and_logs[0], &mut app.compose_content.state);
+    let mut list_state = ListState::default()
+        .with_offset(app.compose_content.state.offset())
+        .with_selected(selected_row);
+    frame.render_stateful_widget(list, main_and_logs[0], &mut list_state);
+    *app.compose_content.state.offset_mut() = list_state.offset();
 
-    let docker_modifiers = create_docker_modifiers(app.compose_content.modifiers);
+    let docker_modifiers =
+        create_docker_modifiers(app.compose_content.modifiers, app.up_all_command_line());
     frame.render_widget(docker_modifiers, main_and_modifier[1]);
 
-    let legend = create_legend(app);
+    let legend = create_legend(app, main_and_legend[1].width);
     frame.render_widget(legend, main_and_legend[1]);
 
     let content = app.compose_content.error_msg.as_deref().unwrap_or_default();
 
-    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
-        .begin_symbol(Some("↑"))
-        .end_symbol(Some("↓"));
-    frame.render_stateful_widget(
-        scrollbar,
-        logs_and_info[0].inner(Margin {
-            vertical: 1,
-            horizontal: 0,
-        }),
-        &mut app.vertical_scroll_state,
-    );
     if app.show_popup {
         let area = frame.area();
 
@@ -154,28 +234,492 @@ pub fn render_main_screen(app: &mut App, frame: &mut Frame) {
             .map(|s| Line::from(s.to_string()))
             .collect::<Vec<_>>(),
         );
+        // The output of a running command follows the latest line, like a terminal.
+        if app.live_output.is_some() {
+            app.popup_scroll = wrapped
+                .height()
+                .saturating_sub(popup_area.height.saturating_sub(2) as usize);
+        }
         app.popup_scroll_state = app
             .popup_scroll_state
             .viewport_content_length(20)
-            .content_length(wrapped.height());
+            .content_length(wrapped.height())
+            .position(app.popup_scroll);
 
+        let (title, border) = if app.live_output.is_some() {
+            ("Output", Style::new().yellow())
+        } else {
+            ("Error", Style::new().red())
+        };
         let popup = Popup::default()
             .content(wrapped)
             .style(Style::new().light_blue().bg(bg))
-            .title("Error")
+            .title(title)
             .title_style(Style::new().white().bold())
-            .border_style(Style::new().red());
+            .border_style(border);
 
         frame.render_stateful_widget(popup, popup_area, &mut app.popup_scroll);
-        frame.render_stateful_widget(
-            Scrollbar::new(ScrollbarOrientation::VerticalRight)
-                .begin_symbol(Some("↑"))
-                .end_symbol(Some("↓")),
-            popup_area.inner(Margin {
-                vertical: 1,
-                horizontal: 0,
-            }),
+        render_scrollbar(
+            frame,
+            popup_area,
             &mut app.popup_scroll_state,
+            app.show_scrollbars,
         );
     }
 }
+
+/// Renders the logs of the selected service on the whole screen, below the container info header.
+pub fn render_maximized_logs(app: &mut App, frame: &mut Frame) {
+    let [header, main] = vertical![==3, >=1].areas(frame.area());
+    frame.render_widget(create_container_info(app, header.width), header);
+    render_logs(app, frame, main);
+}
+
+/// Renders the logs of the selected service with its scrollbar.
+pub fn render_logs(app: &mut App, frame: &mut Frame, area: Rect) {
+    if app.split_active() {
+        render_split_logs(app, frame, area);
+        return;
+    }
+    let bg = get_bg_color();
+    let selected = app.compose_content.state.selected().unwrap_or(0);
+    let content = app.displayed_logs();
+    app.vertical_scroll_state = app
+        .vertical_scroll_state
+        .viewport_content_length(20)
+        .content_length(content.len());
+    // Terminating 3 pixels before is a bit nicer
+    let width = area.width.saturating_sub(3) as usize;
+    let timestamps = app.compose_content.stream_options.timestamps;
+    let mut content = content.join("");
+    let mut marker = app.log_marker_line();
+    if app.compact_logs {
+        let (collapsed, starts) = collapse_entries(&content, timestamps);
+        // The marker goes after the entry it would split.
+        marker = marker.map(|marker| starts.partition_point(|start| *start < marker));
+        content = collapsed;
+    }
+    // Only what arrived since the marker, so the marker itself is left out.
+    let since_marker = marker.filter(|_| app.only_since_marker);
+    if let Some(line) = since_marker {
+        content = split_at_line(&content, line).1.to_string();
+        marker = None;
+    }
+    if let Some(at) = app.log_jump.take() {
+        let text_width = width
+            .saturating_sub(gutter_width(&content, app.show_line_numbers, timestamps))
+            .max(1);
+        let rows = rows_before(&content, text_width, at, app.wrap_chars);
+        // Keep the event in the middle, so the lines right before it are visible too.
+        let half_height = area.height.saturating_sub(2) as usize / 2;
+        app.vertical_scroll = rows.saturating_sub(half_height);
+        app.vertical_scroll_state = app.vertical_scroll_state.position(app.vertical_scroll);
+    }
+    if let Some(query) = &app.search_query {
+        app.search_matches = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !find_matches(line, query).is_empty())
+            .map(|(i, _)| i)
+            .collect();
+    }
+    if let Some(line) = app.search_jump.take() {
+        let text_width = width
+            .saturating_sub(gutter_width(&content, app.show_line_numbers, timestamps))
+            .max(1);
+        let mut rows = rows_before_line(&content, text_width, line, timestamps, app.wrap_chars);
+        if marker.is_some_and(|marker| marker <= line) {
+            rows += 1;
+        }
+        // Keep the match in the middle, so the lines around it are visible too.
+        let half_height = area.height.saturating_sub(2) as usize / 2;
+        app.vertical_scroll = rows.saturating_sub(half_height);
+        app.vertical_scroll_state = app.vertical_scroll_state.position(app.vertical_scroll);
+    }
+    let mut wrapped = if app.show_line_numbers || timestamps {
+        gutter_lines(
+            &content,
+            width,
+            app.show_line_numbers,
+            timestamps,
+            marker,
+            app.wrap_chars,
+        )
+    } else {
+        let wrap = |text: &str| {
+            textwrap::wrap(text, wrap_options(width, app.wrap_chars))
+                .iter()
+                .map(|s| Line::from(s.to_string()))
+                .collect::<Vec<_>>()
+        };
+        match marker {
+            Some(marker) => {
+                let (before, after) = split_at_line(&content, marker);
+                let mut lines = wrap(before);
+                lines.push(marker_line(width));
+                lines.extend(wrap(after));
+                Text::from(lines)
+            }
+            None => Text::from(wrap(&content)),
+        }
+    };
+    if let Some(query) = &app.search_query {
+        highlight_matches(&mut wrapped, query);
+    }
+    if app.auto_scroll {
+        // The borders take up 2 rows.
+        app.vertical_scroll = wrapped
+            .height()
+            .saturating_sub(area.height.saturating_sub(2) as usize);
+        app.vertical_scroll_state = app.vertical_scroll_state.position(app.vertical_scroll);
+    }
+    let mut logs_title = if app.all_logs {
+        String::from("Logs (all services)")
+    } else {
+        match app.compose_content.since_window.get(&selected) {
+            Some(window) => format!("Logs (since {window})"),
+            None => format!("Logs (tail {})", app.compose_content.stream_options.tail),
+        }
+    };
+    if app.compact_logs {
+        logs_title.push_str(" (compact)");
+    }
+    if app.wrap_chars {
+        logs_title.push_str(" (wrap: chars)");
+    }
+    if app.auto_scroll {
+        logs_title.push_str(" (follow)");
+    }
+    if let Some(marker) = marker {
+        let new = content.lines().count().saturating_sub(marker);
+        logs_title.push_str(&format!(" (frozen, {new} new)"));
+    }
+    if since_marker.is_some() {
+        let new = content.lines().count();
+        logs_title.push_str(&format!(" (since the mark, {new} new)"));
+    }
+    if let Some(query) = &app.search_query {
+        logs_title.push_str(&format!(
+            " (/{query}: {} lines, (n) / (N) next / previous)",
+            app.search_matches.len()
+        ));
+    }
+    frame.render_widget(
+        Paragraph::new(wrapped)
+            .block(
+                Block::bordered()
+                    .title(logs_title)
+                    .border_type(BorderType::Rounded)
+                    .style(Style::default().fg(Color::LightBlue).bg(bg)),
+            )
+            .scroll((app.vertical_scroll as _, 0)),
+        area,
+    );
+
+    render_scrollbar(
+        frame,
+        area,
+        &mut app.vertical_scroll_state,
+        app.show_scrollbars,
+    );
+}
+
+/// Renders the logs of the picked services side by side, the focused pane highlighted.
+fn render_split_logs(app: &mut App, frame: &mut Frame, area: Rect) {
+    let bg = get_bg_color();
+    let count = app.split_panes.len();
+    let areas = Layout::horizontal(vec![Constraint::Ratio(1, count as u32); count]).split(area);
+    let timestamps = app.compose_content.stream_options.timestamps;
+    for (i, pane_area) in areas.iter().enumerate() {
+        let pane = app.split_panes[i];
+        let name = app
+            .compose_content
+            .compose
+            .services
+            .0
+            .get_index(pane.service)
+            .map(|(name, _)| name.clone())
+            .unwrap_or_default();
+        let content = app
+            .compose_content
+            .logs
+            .lock()
+            .unwrap()
+            .get(&pane.service)
+            .map(|chunks| chunks.concat())
+            .unwrap_or_default();
+        // Terminating 3 pixels before is a bit nicer
+        let width = pane_area.width.saturating_sub(3) as usize;
+        // The panes are narrow, so the timestamps are left out.
+        let lines = content
+            .lines()
+            .flat_map(|line| {
+                let message = if timestamps {
+                    split_timestamp(line).1
+                } else {
+                    line
+                };
+                textwrap::wrap(message, wrap_options(width, app.wrap_chars))
+                    .into_iter()
+                    .map(|part| Line::from(part.into_owned()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let scroll = pane.scroll.min(lines.len().saturating_sub(1));
+        app.split_panes[i].scroll = scroll;
+        let color = if i == app.split_focus {
+            Color::Red
+        } else {
+            Color::LightBlue
+        };
+        let mut scroll_state = ScrollbarState::new(lines.len()).position(scroll);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(
+                    Block::bordered()
+                        .title(format!("Logs ({name})"))
+                        .border_type(BorderType::Rounded)
+                        .style(Style::default().fg(color).bg(bg)),
+                )
+                .scroll((scroll as _, 0)),
+            *pane_area,
+        );
+        render_scrollbar(frame, *pane_area, &mut scroll_state, app.show_scrollbars);
+    }
+}
+
+/// The width of the relative time column, the longest of the times, or 0 if no line has a timestamp.
+fn relative_time_width(content: &str, now: jiff::Timestamp) -> usize {
+    content
+        .lines()
+        .filter_map(|line| split_timestamp(line).0)
+        .map(|timestamp| format_relative(timestamp, now).width())
+        .max()
+        .unwrap_or(0)
+}
+
+/// The width taken from the log messages by the line number gutter and the relative time column.
+fn gutter_width(content: &str, line_numbers: bool, timestamps: bool) -> usize {
+    let number_width = content.lines().count().max(1).to_string().len();
+    let numbers = if line_numbers { number_width + 1 } else { 0 };
+    let times = match relative_time_width(content, jiff::Timestamp::now()) {
+        width if timestamps && width > 0 => width + 1,
+        _ => 0,
+    };
+    numbers + times
+}
+
+/// The number of rows the timestamped logs take up before the first line logged at or after `at`.
+fn rows_before(content: &str, text_width: usize, at: jiff::Timestamp, wrap_chars: bool) -> usize {
+    let mut rows = 0;
+    for line in content.lines() {
+        let (timestamp, message) = split_timestamp(line);
+        if timestamp.is_some_and(|timestamp| timestamp >= at) {
+            break;
+        }
+        rows += textwrap::wrap(message, wrap_options(text_width, wrap_chars)).len();
+    }
+    rows
+}
+
+/// The number of rows the logs take up before the given line.
+fn rows_before_line(
+    content: &str,
+    text_width: usize,
+    line: usize,
+    timestamps: bool,
+    wrap_chars: bool,
+) -> usize {
+    content
+        .lines()
+        .take(line)
+        .map(|line| {
+            let message = if timestamps {
+                split_timestamp(line).1
+            } else {
+                line
+            };
+            // Empty lines still take up a row.
+            textwrap::wrap(message, wrap_options(text_width, wrap_chars))
+                .len()
+                .max(1)
+        })
+        .sum()
+}
+
+/// Highlights the occurrences of the query in the log messages, the gutters and the marker are left alone.
+fn highlight_matches(text: &mut Text<'static>, query: &str) {
+    let highlight = Style::default().fg(Color::Black).bg(Color::Yellow);
+    for line in &mut text.lines {
+        let spans = std::mem::take(&mut line.spans);
+        for span in spans {
+            if span.style != Style::default() {
+                line.spans.push(span);
+                continue;
+            }
+            let content = span.content.as_ref();
+            let mut last = 0;
+            for range in find_matches(content, query) {
+                if range.start > last {
+                    line.spans
+                        .push(Span::raw(content[last..range.start].to_string()));
+                }
+                line.spans
+                    .push(Span::styled(content[range.clone()].to_string(), highlight));
+                last = range.end;
+            }
+            if last == 0 {
+                line.spans.push(span);
+            } else if last < content.len() {
+                line.spans.push(Span::raw(content[last..].to_string()));
+            }
+        }
+    }
+}
+
+/// The options wrapping the logs at the word boundaries, or at any character if `chars` is set.
+fn wrap_options(width: usize, chars: bool) -> textwrap::Options<'static> {
+    let options = textwrap::Options::new(width);
+    if !chars {
+        return options;
+    }
+    // Every character is a word of its own, so the lines are filled up to the last column.
+    options
+        .word_separator(WordSeparator::Custom(|line| {
+            Box::new(
+                line.char_indices()
+                    .map(|(i, c)| Word::from(&line[i..i + c.len_utf8()])),
+            )
+        }))
+        .wrap_algorithm(WrapAlgorithm::FirstFit)
+}
+
+/// Collapses the continuation lines of each log entry into its first line, noting how many were hidden.
+///
+/// A line continues the previous entry if its message is indented or starts with `Caused by:`,
+/// like the frames of Java and Python stack traces. Returns the collapsed logs and the buffer line
+/// each entry starts at.
+fn collapse_entries(content: &str, timestamps: bool) -> (String, Vec<usize>) {
+    let mut entries: Vec<(&str, usize)> = Vec::new();
+    let mut starts = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let message = if timestamps {
+            split_timestamp(line).1
+        } else {
+            line
+        };
+        let continues = message.starts_with([' ', '\t']) || message.starts_with("Caused by:");
+        match entries.last_mut() {
+            Some((_, hidden)) if continues => *hidden += 1,
+            _ => {
+                entries.push((line, 0));
+                starts.push(i);
+            }
+        }
+    }
+    let collapsed = entries
+        .into_iter()
+        .map(|(line, hidden)| match hidden {
+            0 => format!("{line}\n"),
+            1 => format!("{line} [+1 line]\n"),
+            _ => format!("{line} [+{hidden} lines]\n"),
+        })
+        .collect();
+    (collapsed, starts)
+}
+
+/// Splits the logs before the given line, the second half is empty if there are fewer lines.
+fn split_at_line(content: &str, line: usize) -> (&str, &str) {
+    if line == 0 {
+        return ("", content);
+    }
+    let offset = content
+        .match_indices('\n')
+        .nth(line - 1)
+        .map_or(content.len(), |(i, _)| i + 1);
+    content.split_at(offset)
+}
+
+/// The line separating the logs that arrived after freezing the view.
+fn marker_line(width: usize) -> Line<'static> {
+    Line::styled(
+        format!("{:─^width$}", " new since here "),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Wraps the logs line by line, with an optional gutter for the line number of each buffer line, and
+/// a right-aligned column for its relative time. Continuation lines produced by wrapping leave them
+/// empty, and the marker goes before the `marker` line.
+fn gutter_lines(
+    content: &str,
+    width: usize,
+    line_numbers: bool,
+    timestamps: bool,
+    marker: Option<usize>,
+    wrap_chars: bool,
+) -> Text<'static> {
+    let lines = content.lines().collect::<Vec<_>>();
+    let number_width = if line_numbers {
+        lines.len().max(1).to_string().len()
+    } else {
+        0
+    };
+    // The buffer may have been cleared since, then the marker goes to the end.
+    let marker = marker.map(|marker| marker.min(lines.len()));
+    let now = jiff::Timestamp::now();
+    let time_width = if timestamps {
+        relative_time_width(content, now)
+    } else {
+        0
+    };
+    let gutter_width = gutter_width(content, line_numbers, timestamps);
+    let gutter_style = Style::default().fg(Color::DarkGray);
+    let text_width = width.saturating_sub(gutter_width).max(1);
+
+    let mut result = Vec::with_capacity(lines.len());
+    for (i, line) in lines.iter().enumerate() {
+        if marker == Some(i) {
+            result.push(marker_line(width));
+        }
+        let (timestamp, message) = if timestamps {
+            split_timestamp(line)
+        } else {
+            (None, *line)
+        };
+        for (j, part) in textwrap::wrap(message, wrap_options(text_width, wrap_chars))
+            .iter()
+            .enumerate()
+        {
+            let mut spans = Vec::with_capacity(3);
+            if line_numbers {
+                let number = if j == 0 {
+                    format!("{:>number_width$} ", i + 1)
+                } else {
+                    " ".repeat(number_width + 1)
+                };
+                spans.push(Span::styled(number, gutter_style));
+            }
+            spans.push(Span::raw(part.to_string()));
+            if let Some(timestamp) = timestamp.filter(|_| j == 0 && time_width > 0) {
+                // The message is padded to the full width, so the times line up on the right.
+                let padding = text_width.saturating_sub(part.width());
+                spans.push(Span::styled(
+                    format!(
+                        "{} {:>time_width$}",
+                        " ".repeat(padding),
+                        format_relative(timestamp, now)
+                    ),
+                    gutter_style,
+                ));
+            }
+            result.push(Line::from(spans));
+        }
+    }
+    if marker == Some(lines.len()) {
+        result.push(marker_line(width));
+    }
+    Text::from(result)
+}
@@ -1,27 +1,96 @@
+mod activity;
+mod build;
+mod compose_ps;
 mod container_details;
+mod daemon_events;
+mod dashboard;
+mod dependencies;
+mod diagnostics;
+mod diff;
+mod environment;
+mod file_info;
+mod grep;
 mod help;
 mod legend;
 mod main_screen;
+mod names;
+mod panel;
 mod popup;
 mod resize_screen;
+mod resources;
 
-use ratatui::{style::Color, Frame};
+use ratatui::{
+    layout::{Margin, Rect},
+    style::Color,
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
 
-use crate::{app::App, handler::AlternateScreenContent, LIGHT_MODE};
+use crate::{app::App, handler::AlternateScreenContent, LIGHT_MODE, MIN_COLS, MIN_ROWS};
 
 const UNNAMED: &str = "<unnamed>";
 const UNSPECIFIED: &str = "<unspecified>";
 const ALL_INTERFACES: &str = "0.0.0.0";
-const MIN_ROWS: u16 = 20;
-const MIN_COLS: u16 = 130;
+/// The default minimum size of the main screen, the legend and the modifiers need the most space.
+pub const DEFAULT_MIN_ROWS: u16 = 20;
+pub const DEFAULT_MIN_COLS: u16 = 130;
+/// The minimum size of the details screen, the header and the four quadrants.
+const MIN_DETAILS_ROWS: u16 = 20;
+const MIN_DETAILS_COLS: u16 = 100;
+/// The minimum size of the help screen, the keys are wrapped so it's narrower.
+const MIN_HELP_ROWS: u16 = 12;
+const MIN_HELP_COLS: u16 = 80;
+/// The minimum size of the single panel screens.
+const MIN_PANEL_ROWS: u16 = 10;
+const MIN_PANEL_COLS: u16 = 80;
 
 const BG_LIGHT: Color = Color::White;
 const BG_DARK: Color = Color::Black;
 
+/// The name and the minimum (columns, rows) required by the screen about to render.
+fn min_size(content: AlternateScreenContent) -> (&'static str, u16, u16) {
+    match content {
+        AlternateScreenContent::Help => ("help", MIN_HELP_COLS, MIN_HELP_ROWS),
+        AlternateScreenContent::ContainerDetails(_) => {
+            ("details", MIN_DETAILS_COLS, MIN_DETAILS_ROWS)
+        }
+        AlternateScreenContent::Diff
+        | AlternateScreenContent::Environment
+        | AlternateScreenContent::Resources
+        | AlternateScreenContent::ComposePs
+        | AlternateScreenContent::Dashboard
+        | AlternateScreenContent::Build
+        | AlternateScreenContent::Diagnostics
+        | AlternateScreenContent::Names
+        | AlternateScreenContent::Activity
+        | AlternateScreenContent::DaemonEvents
+        | AlternateScreenContent::FileInfo
+        | AlternateScreenContent::Dependencies
+        | AlternateScreenContent::Grep => ("panel", MIN_PANEL_COLS, MIN_PANEL_ROWS),
+        AlternateScreenContent::Logs => ("logs", MIN_PANEL_COLS, MIN_PANEL_ROWS),
+        AlternateScreenContent::None => {
+            let (cols, rows) = main_min_size();
+            ("main", cols, rows)
+        }
+    }
+}
+
+/// The minimum (columns, rows) of the main screen, as set on the command line.
+fn main_min_size() -> (u16, u16) {
+    (
+        MIN_COLS.get().copied().unwrap_or(DEFAULT_MIN_COLS),
+        MIN_ROWS.get().copied().unwrap_or(DEFAULT_MIN_ROWS),
+    )
+}
+
 pub fn render(app: &mut App, frame: &mut Frame) {
     let size = frame.area();
-    if size.width < MIN_COLS || size.height < MIN_ROWS {
-        frame.render_widget(resize_screen::ResizeScreen::new(), frame.area());
+    let (screen, min_cols, min_rows) = min_size(app.alternate_screen_content);
+    if size.width < min_cols || size.height < min_rows {
+        frame.render_widget(
+            resize_screen::ResizeScreen::new(screen, min_cols, min_rows),
+            frame.area(),
+        );
         return;
     }
     match app.alternate_screen_content {
@@ -31,8 +100,60 @@ pub fn render(app: &mut App, frame: &mut Frame) {
             container_details::render_container_details(app, frame, i)
         }
 
+        AlternateScreenContent::Diff => diff::render_diff(app, frame),
+
+        AlternateScreenContent::Environment => environment::render_environment(app, frame),
+
+        AlternateScreenContent::Resources => resources::render_resources(app, frame),
+
+        AlternateScreenContent::ComposePs => compose_ps::render_compose_ps(app, frame),
+
+        AlternateScreenContent::Dashboard => dashboard::render_dashboard(app, frame),
+
+        AlternateScreenContent::Build => build::render_build(app, frame),
+
+        AlternateScreenContent::Diagnostics => diagnostics::render_diagnostics(app, frame),
+
+        AlternateScreenContent::Names => names::render_names(app, frame),
+
+        AlternateScreenContent::Activity => activity::render_activity(app, frame),
+
+        AlternateScreenContent::DaemonEvents => daemon_events::render_daemon_events(app, frame),
+
+        AlternateScreenContent::FileInfo => file_info::render_file_info(app, frame),
+
+        AlternateScreenContent::Dependencies => dependencies::render_dependencies(app, frame),
+
+        AlternateScreenContent::Grep => grep::render_grep(app, frame),
+
+        AlternateScreenContent::Logs => main_screen::render_maximized_logs(app, frame),
+
         AlternateScreenContent::None => main_screen::render_main_screen(app, frame),
     }
+
+    if let Some(confirm) = &app.confirm {
+        popup::render_confirm(frame, confirm);
+    }
+    if let Some(prompt) = &app.prompt {
+        popup::render_prompt(frame, prompt);
+    }
+}
+
+/// Renders the vertical scrollbar on the right border of the area, unless the scrollbars are hidden.
+fn render_scrollbar(frame: &mut Frame, area: Rect, state: &mut ScrollbarState, show: bool) {
+    if !show {
+        return;
+    }
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓")),
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        state,
+    );
 }
 
 pub fn get_bg_color() -> Color {
@@ -0,0 +1,67 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr as _;
+
+use super::panel::render_panel;
+use crate::app::App;
+
+/// Shows the container name derived for each service next to the one compose actually created.
+pub fn render_names(app: &mut App, frame: &mut Frame) {
+    let services = app
+        .compose_content
+        .compose
+        .services
+        .0
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    let name_width = services
+        .iter()
+        .map(|name| name.width())
+        .max()
+        .unwrap_or_default();
+    let guessed_width = app
+        .container_name_mapping
+        .values()
+        .map(|name| name.width())
+        .max()
+        .unwrap_or_default();
+
+    let header_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let value_style = Style::default().fg(Color::LightYellow);
+    let detail_style = Style::default().fg(Color::Gray);
+    let mismatch_style = Style::default().add_modifier(Modifier::BOLD).fg(Color::Red);
+
+    let mut lines = vec![Line::styled(
+        format!(
+            "{:name_width$}  {:guessed_width$}  actual",
+            "service", "guessed"
+        ),
+        header_style,
+    )];
+    for (i, service) in services.iter().enumerate() {
+        let guessed = app
+            .container_name_mapping
+            .get(&i)
+            .cloned()
+            .unwrap_or_default();
+        let actual = app.labeled_container_name(i).map(String::from);
+        let (actual, style) = match actual {
+            Some(actual) if actual == guessed => (actual, value_style),
+            Some(actual) => (format!("{actual} (mismatch)"), mismatch_style),
+            None => (String::from("<no container>"), detail_style),
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("{service:name_width$}  ")),
+            Span::styled(format!("{guessed:guessed_width$}  "), detail_style),
+            Span::styled(actual, style),
+        ]));
+    }
+
+    render_panel(app, frame, "Container names", lines);
+}
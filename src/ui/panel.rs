@@ -0,0 +1,42 @@
+use ratatui::{
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use ratatui_macros::vertical;
+
+use super::{get_bg_color, legend::create_container_info, render_scrollbar};
+use crate::app::App;
+
+/// Renders a single scrollable panel below the container info header.
+pub fn render_panel(app: &mut App, frame: &mut Frame, title: &str, lines: Vec<Line<'static>>) {
+    let bg = get_bg_color();
+    let [header, main] = vertical![==3, >=1].areas(frame.area());
+
+    app.alternate_screen.panel_scroll_state = app
+        .alternate_screen
+        .panel_scroll_state
+        .viewport_content_length(20)
+        .content_length(lines.len());
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((app.alternate_screen.panel_scroll as _, 0))
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::LightBlue).bg(bg)),
+            ),
+        main,
+    );
+    render_scrollbar(
+        frame,
+        main,
+        &mut app.alternate_screen.panel_scroll_state,
+        app.show_scrollbars,
+    );
+    frame.render_widget(create_container_info(app, header.width), header);
+}
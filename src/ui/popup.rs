@@ -2,10 +2,16 @@ use derive_setters::Setters;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::Style,
+    style::{Style, Stylize},
     text::{Line, Text},
     widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget, Wrap},
+    Frame,
 };
+use ratatui_macros::{horizontal, vertical};
+
+use crate::handler::{Confirm, Prompt};
+
+use super::get_bg_color;
 
 #[derive(Debug, Default, Setters)]
 pub struct Popup<'a> {
@@ -35,3 +41,39 @@ impl StatefulWidget for Popup<'_> {
             .render(area, buf);
     }
 }
+
+/// Renders the confirmation prompt in the middle of the screen.
+pub fn render_confirm(frame: &mut Frame, confirm: &Confirm) {
+    let prompt = confirm.prompt();
+    // The affected containers may take up a few rows, the borders take up 2 columns and 2 rows.
+    let rows = (textwrap::wrap(&prompt, 68).len() as u16 + 2).max(8);
+    let [_, area, _] = vertical![>=0, ==rows, >=0].areas(frame.area());
+    let [_, area, _] = horizontal![>=0, ==70, >=0].areas(area);
+    let popup = Popup::default()
+        .content(prompt)
+        .style(Style::new().light_blue().bg(get_bg_color()))
+        .title("Confirm")
+        .title_style(Style::new().white().bold())
+        .border_style(Style::new().yellow());
+    frame.render_stateful_widget(popup, area, &mut 0);
+}
+
+/// Renders the text input prompt in the middle of the screen.
+pub fn render_prompt(frame: &mut Frame, prompt: &Prompt) {
+    let mut content = String::new();
+    if !prompt.affected.is_empty() {
+        content.push_str(&format!("Affected: {}\n\n", prompt.affected.join(", ")));
+    }
+    content.push_str(&format!("{}█", prompt.input));
+    // The borders take up 2 columns and 2 rows.
+    let rows = textwrap::wrap(&content, 68).len() as u16 + 2;
+    let [_, area, _] = vertical![>=0, ==rows, >=0].areas(frame.area());
+    let [_, area, _] = horizontal![>=0, ==70, >=0].areas(area);
+    let popup = Popup::default()
+        .content(content)
+        .style(Style::new().white().bg(get_bg_color()))
+        .title(prompt.kind.title())
+        .title_style(Style::new().white().bold())
+        .border_style(Style::new().yellow());
+    frame.render_stateful_widget(popup, area, &mut 0);
+}
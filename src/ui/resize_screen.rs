@@ -6,25 +6,28 @@ use ratatui::{
 };
 use ratatui_macros::vertical;
 
-use super::{MIN_COLS, MIN_ROWS};
+use super::main_min_size;
 
 #[derive(Debug)]
 pub struct ResizeScreen {
+    pub screen: &'static str,
     pub min_height: u16,
     pub min_width: u16,
 }
 
 impl Default for ResizeScreen {
     fn default() -> Self {
-        Self::new()
+        let (cols, rows) = main_min_size();
+        Self::new("main", cols, rows)
     }
 }
 
 impl ResizeScreen {
-    pub fn new() -> Self {
+    pub fn new(screen: &'static str, min_width: u16, min_height: u16) -> Self {
         Self {
-            min_width: MIN_COLS,
-            min_height: MIN_ROWS,
+            screen,
+            min_width,
+            min_height,
         }
     }
 }
@@ -62,7 +65,10 @@ impl Widget for ResizeScreen {
                 height_span,
             ]),
             Line::from(""),
-            Line::from("Required dimensions:"),
+            Line::from(format!(
+                "Required dimensions for the {} screen:",
+                self.screen
+            )),
             Line::from(vec![
                 Span::from(format!("Width = {}", self.min_width)),
                 Span::from(", ".to_string()),
@@ -0,0 +1,168 @@
+use docker_compose_types::{
+    ComposeNetwork, ExternalVolume, MapOrEmpty, Networks, Service, Volumes,
+};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    Frame,
+};
+
+use super::panel::render_panel;
+use crate::app::App;
+
+/// The network every service without explicit networks is attached to.
+const DEFAULT_NETWORK: &str = "default";
+
+pub fn render_resources(app: &mut App, frame: &mut Frame) {
+    let compose = &app.compose_content.compose;
+    let services = compose
+        .services
+        .0
+        .iter()
+        .map(|(name, service)| (name.as_str(), service.clone().unwrap_or_default()))
+        .collect::<Vec<_>>();
+
+    let section_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let name_style = Style::default()
+        .fg(Color::LightYellow)
+        .add_modifier(Modifier::BOLD);
+    let detail_style = Style::default().fg(Color::Gray);
+
+    let mut lines = vec![Line::styled("Networks", section_style)];
+    let mut networks = compose
+        .networks
+        .0
+        .iter()
+        .map(|(name, settings)| {
+            let mut details = Vec::new();
+            if let MapOrEmpty::Map(settings) = settings {
+                if let Some(driver) = &settings.driver {
+                    details.push(format!("driver: {driver}"));
+                }
+                if matches!(
+                    settings.external,
+                    Some(ComposeNetwork::Bool(true) | ComposeNetwork::Detailed(_))
+                ) {
+                    details.push(String::from("external"));
+                }
+                if settings.internal {
+                    details.push(String::from("internal"));
+                }
+            }
+            (name.clone(), details)
+        })
+        .collect::<Vec<_>>();
+    let uses_default = services
+        .iter()
+        .any(|(_, service)| service.network_mode.is_none() && service.networks.is_empty());
+    if uses_default && !compose.networks.0.contains_key(DEFAULT_NETWORK) {
+        networks.push((
+            String::from(DEFAULT_NETWORK),
+            vec![String::from("implicit")],
+        ));
+    }
+    if networks.is_empty() {
+        lines.push(Line::styled("  <none>", detail_style));
+    }
+    for (name, details) in networks {
+        let attached = services
+            .iter()
+            .filter(|(_, service)| service_networks(service).contains(&name))
+            .map(|(service_name, _)| *service_name)
+            .collect::<Vec<_>>();
+        lines.extend(resource_lines(
+            &name,
+            &details,
+            &attached,
+            name_style,
+            detail_style,
+        ));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::styled("Volumes", section_style));
+    if compose.volumes.0.is_empty() {
+        lines.push(Line::styled("  <none>", detail_style));
+    }
+    for (name, settings) in &compose.volumes.0 {
+        let mut details = Vec::new();
+        if let MapOrEmpty::Map(settings) = settings {
+            if let Some(driver) = &settings.driver {
+                details.push(format!("driver: {driver}"));
+            }
+            if matches!(
+                settings.external,
+                Some(ExternalVolume::Bool(true) | ExternalVolume::Name { .. })
+            ) {
+                details.push(String::from("external"));
+            }
+        }
+        let attached = services
+            .iter()
+            .filter(|(_, service)| service_named_volumes(service).contains(name))
+            .map(|(service_name, _)| *service_name)
+            .collect::<Vec<_>>();
+        lines.extend(resource_lines(
+            name,
+            &details,
+            &attached,
+            name_style,
+            detail_style,
+        ));
+    }
+
+    render_panel(app, frame, "Top-level networks and volumes", lines);
+}
+
+fn resource_lines(
+    name: &str,
+    details: &[String],
+    attached: &[&str],
+    name_style: Style,
+    detail_style: Style,
+) -> Vec<Line<'static>> {
+    let mut header = vec![Span::styled(format!("  {name}"), name_style)];
+    if !details.is_empty() {
+        header.push(Span::styled(
+            format!(" ({})", details.join(", ")),
+            detail_style,
+        ));
+    }
+    let services = if attached.is_empty() {
+        String::from("    no services attached")
+    } else {
+        format!("    services: {}", attached.join(", "))
+    };
+    vec![Line::from(header), Line::raw(services)]
+}
+
+/// The networks the service is attached to.
+fn service_networks(service: &Service) -> Vec<String> {
+    if service.network_mode.is_some() {
+        return Vec::new();
+    }
+    match &service.networks {
+        Networks::Simple(networks) if networks.is_empty() => vec![String::from(DEFAULT_NETWORK)],
+        Networks::Simple(networks) => networks.clone(),
+        Networks::Advanced(networks) => networks.0.keys().cloned().collect(),
+    }
+}
+
+/// The named volumes the service mounts, bind mounts are skipped.
+fn service_named_volumes(service: &Service) -> Vec<String> {
+    service
+        .volumes
+        .iter()
+        .filter_map(|volume| match volume {
+            Volumes::Simple(volume) => {
+                let (source, _) = volume.split_once(':')?;
+                let is_path = source.starts_with(['/', '.', '~', '$']);
+                (!is_path).then(|| source.to_string())
+            }
+            Volumes::Advanced(volume) if volume._type == "volume" => volume.source.clone(),
+            Volumes::Advanced(_) => None,
+        })
+        .collect()
+}
@@ -35,3 +35,194 @@ pub fn shorten_path(path: impl AsRef<Path>) -> PathBuf {
             acc
         })
 }
+
+/// Substitutes `$VAR`, `${VAR}`, `${VAR:-default}` and `${VAR-default}` from the process environment
+/// the same way Docker Compose does. `$$` is an escaped `$`.
+pub fn interpolate(value: &str) -> String {
+    interpolate_with(value, |name| std::env::var(name).ok())
+}
+
+/// Same as [`interpolate`], but variables are resolved with `lookup`.
+pub fn interpolate_with(value: &str, mut lookup: impl FnMut(&str) -> Option<String>) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            result.push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some('$') => {
+                chars.next();
+                result.push('$');
+            }
+            Some('{') => {
+                chars.next();
+                let expr: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                let (name, default, default_if_empty) = if let Some((n, d)) = expr.split_once(":-")
+                {
+                    (n, Some(d), true)
+                } else if let Some((n, d)) = expr.split_once('-') {
+                    (n, Some(d), false)
+                } else {
+                    (expr.as_str(), None, false)
+                };
+                match (lookup(name), default) {
+                    (Some(v), Some(d)) if v.is_empty() && default_if_empty => result.push_str(d),
+                    (Some(v), _) => result.push_str(&v),
+                    (None, Some(d)) => result.push_str(d),
+                    (None, None) => {}
+                }
+            }
+            Some(c) if c.is_ascii_alphabetic() || *c == '_' => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                result.push_str(&lookup(&name).unwrap_or_default());
+            }
+            _ => result.push('$'),
+        }
+    }
+    result
+}
+
+/// Splits the leading RFC3339 timestamp off a log line, as added by Docker when timestamps are enabled.
+/// Returns the line unchanged if it doesn't start with a valid timestamp.
+pub fn split_timestamp(line: &str) -> (Option<jiff::Timestamp>, &str) {
+    match line.split_once(' ') {
+        Some((timestamp, message)) => match timestamp.parse::<jiff::Timestamp>() {
+            Ok(timestamp) => (Some(timestamp), message),
+            Err(_) => (None, line),
+        },
+        None => (None, line),
+    }
+}
+
+/// Parses a relative time like "5m", "1h30m" or "90s ago" into seconds.
+pub fn parse_relative(input: &str) -> anyhow::Result<i64> {
+    let input = input.trim();
+    let input = input.strip_suffix("ago").unwrap_or(input).trim();
+    anyhow::ensure!(!input.is_empty(), "Missing relative time");
+
+    let mut total = 0;
+    let mut number = String::new();
+    for ch in input.chars().filter(|ch| !ch.is_whitespace()) {
+        if ch.is_ascii_digit() {
+            number.push(ch);
+            continue;
+        }
+        let unit = match ch {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            _ => anyhow::bail!("Unknown time unit '{ch}' in \"{input}\", use s, m, h or d"),
+        };
+        let value = number
+            .parse::<i64>()
+            .map_err(|_| anyhow::anyhow!("Missing number before '{ch}' in \"{input}\""))?;
+        total += value * unit;
+        number.clear();
+    }
+    anyhow::ensure!(
+        number.is_empty(),
+        "Missing time unit after {number} in \"{input}\""
+    );
+    Ok(total)
+}
+
+/// Formats the time elapsed since `timestamp` in a short human readable way, like "2s ago".
+pub fn format_relative(timestamp: jiff::Timestamp, now: jiff::Timestamp) -> String {
+    let secs = now.duration_since(timestamp).as_secs();
+    match secs {
+        ..=0 => String::from("now"),
+        1..=59 => format!("{secs}s ago"),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+/// Copies the text to the system clipboard with the OSC 52 escape sequence, so it works over SSH too.
+pub fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    use base64::Engine as _;
+    use std::io::Write as _;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stderr = std::io::stderr();
+    write!(stderr, "\x1b]52;c;{encoded}\x07")?;
+    stderr.flush()
+}
+
+/// Truncates the text to fit into `width` columns, marking the cut with an ellipsis.
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    use unicode_width::{UnicodeWidthChar as _, UnicodeWidthStr as _};
+
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut result = String::new();
+    let mut used = 0;
+    // Leave room for the ellipsis.
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width + 1 > width {
+            break;
+        }
+        used += ch_width;
+        result.push(ch);
+    }
+    if width > 0 {
+        result.push('…');
+    }
+    result
+}
+
+/// Shrinks the widest of the `widths` until their sum fits into `budget`, leaving the narrow ones intact.
+pub fn fit_widths(widths: &[usize], budget: usize) -> Vec<usize> {
+    let mut cap = widths.iter().copied().max().unwrap_or_default();
+    while cap > 0 && widths.iter().map(|w| (*w).min(cap)).sum::<usize>() > budget {
+        cap -= 1;
+    }
+    widths.iter().map(|w| (*w).min(cap)).collect()
+}
+
+/// The byte ranges of the case-insensitive, non-overlapping occurrences of the query in the text.
+pub fn find_matches(text: &str, query: &str) -> Vec<std::ops::Range<usize>> {
+    let mut matches = Vec::new();
+    let mut from = 0;
+    while from < text.len() {
+        match match_end(&text[from..], query) {
+            Some(end) if end > 0 => {
+                matches.push(from..from + end);
+                from += end;
+            }
+            _ => {
+                from += text[from..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+    }
+    matches
+}
+
+/// Whether the characters of the query appear in the text in order, ignoring the case, like `wkr` in `worker`.
+pub fn fuzzy_match(text: &str, query: &str) -> bool {
+    let mut chars = text.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|q| chars.any(|c| c == q))
+}
+
+/// The length of the prefix of the text that matches the query, ignoring the case.
+fn match_end(text: &str, query: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for q in query.chars() {
+        let (_, c) = chars.next()?;
+        if !c.to_lowercase().eq(q.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(text.len(), |(i, _)| i))
+}